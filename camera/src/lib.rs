@@ -0,0 +1,133 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use model::game_configuration::GameConfiguration;
+
+/// Factor the camera scale changes by per scrolled line
+const ZOOM_STEP: f32 = 1.1;
+/// Scroll distance of touchpads and other pixel based devices that counts as one line
+const PIXELS_PER_LINE: f32 = 20.;
+/// Screen pixels per second the map moves while panning with the keyboard or the window edges
+const PAN_SPEED: f32 = 600.;
+/// Distance from the window border in which the cursor starts to move the map
+const EDGE_PAN_MARGIN: f32 = 10.;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(init_camera_system)
+            .add_system(zoom_system)
+            .add_system(drag_pan_system)
+            .add_system(scroll_pan_system);
+    }
+}
+
+fn init_camera_system(mut commands: Commands) {
+    commands.spawn_bundle(Camera2dBundle::default());
+}
+
+/// Zooms with the mouse wheel while keeping the world location below the cursor in place
+fn zoom_system(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    windows: Res<Windows>,
+    game_configuration: Res<GameConfiguration>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    let scrolled_lines: f32 = mouse_wheel_events
+        .iter()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        })
+        .sum();
+    if scrolled_lines == 0. {
+        return;
+    }
+
+    let (mut transform, mut projection) = camera_query.single_mut();
+    let scale = (projection.scale * ZOOM_STEP.powf(-scrolled_lines))
+        .max(game_configuration.zoom_scale_min)
+        .min(game_configuration.zoom_scale_max);
+
+    if let Some(cursor_offset) = cursor_offset(&windows) {
+        let world_location = transform.translation.truncate() + cursor_offset * projection.scale;
+        let translation = world_location - cursor_offset * scale;
+        transform.translation = translation.extend(transform.translation.z);
+    }
+    projection.scale = scale;
+}
+
+/// Moves the map along with the cursor while the middle mouse button is held down
+fn drag_pan_system(
+    mouse_buttons: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    mut last_cursor_offset: Local<Option<Vec2>>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    if !mouse_buttons.pressed(MouseButton::Middle) {
+        *last_cursor_offset = None;
+        return;
+    }
+
+    let cursor_offset = cursor_offset(&windows);
+    if let (Some(cursor_offset), Some(last_cursor_offset)) = (cursor_offset, *last_cursor_offset) {
+        let (mut transform, projection) = camera_query.single_mut();
+        let movement = (cursor_offset - last_cursor_offset) * projection.scale;
+        transform.translation -= movement.extend(0.);
+    }
+    *last_cursor_offset = cursor_offset;
+}
+
+/// Moves the map with WASD or when the cursor touches the window edges
+fn scroll_pan_system(
+    time: Res<Time>,
+    keyboard: Res<Input<KeyCode>>,
+    windows: Res<Windows>,
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<Camera2d>>,
+) {
+    let mut direction = Vec2::ZERO;
+    if keyboard.pressed(KeyCode::W) {
+        direction.y += 1.;
+    }
+    if keyboard.pressed(KeyCode::S) {
+        direction.y -= 1.;
+    }
+    if keyboard.pressed(KeyCode::A) {
+        direction.x -= 1.;
+    }
+    if keyboard.pressed(KeyCode::D) {
+        direction.x += 1.;
+    }
+
+    if let Some(window) = windows.get_primary() {
+        if let Some(cursor_position) = window.cursor_position() {
+            if cursor_position.x < EDGE_PAN_MARGIN {
+                direction.x -= 1.;
+            } else if cursor_position.x > window.width() - EDGE_PAN_MARGIN {
+                direction.x += 1.;
+            }
+            if cursor_position.y < EDGE_PAN_MARGIN {
+                direction.y -= 1.;
+            } else if cursor_position.y > window.height() - EDGE_PAN_MARGIN {
+                direction.y += 1.;
+            }
+        }
+    }
+
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    let (mut transform, projection) = camera_query.single_mut();
+    let movement = direction.normalize() * PAN_SPEED * projection.scale * time.delta_seconds();
+    transform.translation += movement.extend(0.);
+}
+
+/// Cursor position relative to the center of the window, in screen pixels
+fn cursor_offset(windows: &Windows) -> Option<Vec2> {
+    let window = windows.get_primary()?;
+    let window_size = Vec2::new(window.width(), window.height());
+    window
+        .cursor_position()
+        .map(|cursor_position| cursor_position - window_size / 2.)
+}
@@ -1,16 +1,20 @@
 use bevy::{prelude::*, utils::HashSet};
-use model::game_configuration::GameConfiguration;
+use geo::{coord, Line};
+use model::construction_registry::ConstructionRegistry;
 use model::{
     collision::{CollisionAware, CollisionEvent, CollisionStatus, Collisions},
+    connection::Connection,
     construction::Construction,
+    simulation::SimulationApp,
+    RemovalEvent,
 };
 
 pub struct CollisionPlugin;
 
 impl Plugin for CollisionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(on_construction_enter_system)
-            .add_system(update_construction_collisions_system);
+        app.add_simulation_system(on_construction_enter_system)
+            .add_simulation_system(update_construction_collisions_system);
     }
 }
 
@@ -27,14 +31,36 @@ fn on_construction_enter_system(mut commands: Commands, query: Query<Entity, Add
     }
 }
 
+/// Re-evaluates the collisions of changed constructions. Whenever a connection is added or removed,
+/// the collisions of all constructions are re-evaluated, as any of them may overlap it.
+#[allow(clippy::too_many_arguments)]
 fn update_construction_collisions_system(
-    changed_query: Query<(Entity, &Construction), (Changed<Construction>, With<CollisionAware>)>,
+    changed_query: Query<Entity, (Changed<Construction>, With<CollisionAware>)>,
     construction_query: Query<(Entity, &Construction), With<CollisionAware>>,
-    game_configuration: Res<GameConfiguration>,
+    added_connection_query: Query<(), Added<Connection>>,
+    connection_query: Query<(Entity, &Connection)>,
+    mut connection_removal_events: EventReader<RemovalEvent<Connection>>,
+    construction_registry: Res<ConstructionRegistry>,
     mut collisions_query: Query<&mut Collisions>,
     mut event_writer: EventWriter<CollisionEvent>,
 ) {
-    for (changed_entity, changed_construction) in changed_query.iter() {
+    let removed_connections: HashSet<Entity> = connection_removal_events
+        .iter()
+        .map(|event| event.entity)
+        .collect();
+    let connections_changed = !removed_connections.is_empty() || !added_connection_query.is_empty();
+
+    let changed_entities: Vec<Entity> = if connections_changed {
+        construction_query
+            .iter()
+            .map(|(entity, _)| entity)
+            .collect()
+    } else {
+        changed_query.iter().collect()
+    };
+
+    for changed_entity in changed_entities {
+        let (_, changed_construction) = construction_query.get(changed_entity).unwrap();
         // Drain existing collisions
         let previous_collision: HashSet<_> = {
             let mut collisions = collisions_query.get_mut(changed_entity).unwrap();
@@ -47,7 +73,7 @@ fn update_construction_collisions_system(
             .filter(|(entity, _)| *entity != changed_entity)
             .filter(|(_, construction)| {
                 logic::construction::collides_with(
-                    &game_configuration,
+                    &construction_registry,
                     construction,
                     changed_construction,
                 )
@@ -60,6 +86,36 @@ fn update_construction_collisions_system(
             other_collisions.as_mut().0.insert(changed_entity);
         }
 
+        // Connections of the construction itself start at its center, so they are skipped
+        let overlapped_connections = connection_query
+            .iter()
+            .filter(|(entity, _)| !removed_connections.contains(entity))
+            .filter(|(_, connection)| !connection.connects_to(changed_entity))
+            .filter(|(_, connection)| {
+                let between = connection.between();
+                match (
+                    construction_query.get(between.0),
+                    construction_query.get(between.1),
+                ) {
+                    (Ok((_, construction1)), Ok((_, construction2))) => {
+                        let connection_line = Line::new(
+                            coord! { x: construction1.location.x, y: construction1.location.y },
+                            coord! { x: construction2.location.x, y: construction2.location.y },
+                        );
+                        logic::construction::overlaps_connection(
+                            &construction_registry,
+                            changed_construction,
+                            &connection_line,
+                        )
+                    }
+                    _ => false,
+                }
+            });
+        for (connection_entity, _) in overlapped_connections {
+            let mut changed_collisions = collisions_query.get_mut(changed_entity).unwrap();
+            changed_collisions.as_mut().0.insert(connection_entity);
+        }
+
         let updated_collisions = collisions_query.get(changed_entity).unwrap();
 
         // Send event for new/added collisions
@@ -0,0 +1,191 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::{entity::ShapeBundle, prelude::*, shapes::Circle};
+use model::{
+    connection::Connection,
+    construction::{Construction, ConstructionStatus, UnderConstructionMarker},
+    construction_registry::ConstructionRegistry,
+    game_configuration::GameConfiguration,
+    RemovalEvent,
+};
+
+const Z_VALUE: f32 = 1.;
+
+const DASH_LENGTH: f32 = 8.;
+const DASH_GAP: f32 = 6.;
+/// Candidates dropped because of a crossing are dotted instead of dashed
+const DOT_LENGTH: f32 = 2.;
+
+const PREVIEW_COLOR: Color = Color::CYAN;
+const PREVIEW_CROSSING_COLOR: Color = Color::RED;
+
+const RANGE_COLOR: Color = Color::Rgba {
+    red: 1.,
+    green: 1.,
+    blue: 1.,
+    alpha: 0.2,
+};
+
+/// Dashed line of a connection that would be created by building the hovering construction
+#[derive(Component, Debug)]
+pub(crate) struct BuildConnectionPreview;
+
+/// Circle around a placed construction, within which the hovering construction can be connected
+/// to it
+#[derive(Component, Debug)]
+pub(crate) struct ConnectionRangePreview;
+
+/// Shows the connections that would be created when building the hovering construction at its
+/// current location. Candidates that are dropped because they cross an existing connection are
+/// shown as well, in a different style. The preview is redrawn whenever the hovering construction
+/// moves, or a connection or placed construction is added or removed.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_connection_preview_system(
+    mut commands: Commands,
+    hovering_query: Query<&Construction, With<UnderConstructionMarker>>,
+    changed_hovering_query: Query<(), (With<UnderConstructionMarker>, Changed<Construction>)>,
+    changed_placed_query: Query<(), (Without<UnderConstructionMarker>, Changed<Construction>)>,
+    added_connection_query: Query<(), Added<Connection>>,
+    mut construction_removal_events: EventReader<RemovalEvent<Construction>>,
+    mut connection_removal_events: EventReader<RemovalEvent<Connection>>,
+    preview_query: Query<Entity, With<BuildConnectionPreview>>,
+    construction_query: Query<(Entity, &Construction)>,
+    connection_query: Query<&Connection>,
+    game_configuration: Res<GameConfiguration>,
+    construction_registry: Res<ConstructionRegistry>,
+) {
+    let construction = match hovering_query.iter().next() {
+        Some(construction) => construction,
+        None => {
+            for preview_entity in preview_query.iter() {
+                commands.entity(preview_entity).despawn();
+            }
+            return;
+        }
+    };
+
+    let constructions_removed = construction_removal_events.iter().count() > 0;
+    let connections_removed = connection_removal_events.iter().count() > 0;
+    if changed_hovering_query.is_empty()
+        && changed_placed_query.is_empty()
+        && added_connection_query.is_empty()
+        && !constructions_removed
+        && !connections_removed
+    {
+        return;
+    }
+
+    for preview_entity in preview_query.iter() {
+        commands.entity(preview_entity).despawn();
+    }
+
+    let constructions = construction_query.iter().collect();
+    let candidates = logic::construction::connection_candidates(
+        &game_configuration,
+        &construction_registry,
+        &constructions,
+        &logic::construction::connection_lines(&constructions, &connection_query.iter().collect()),
+        &construction.location,
+        &construction.kind,
+    );
+    for candidate in candidates.iter() {
+        commands
+            .spawn_bundle(preview_bundle(
+                candidate.location,
+                construction.location,
+                candidate.crosses_connection,
+            ))
+            .insert(BuildConnectionPreview);
+    }
+}
+
+/// Shows the connection range of every placed construction while in build mode. As the range
+/// depends on both kinds, it is drawn for the kind that is about to be built. The ranges are
+/// redrawn whenever a construction is placed, changed or removed while the build mode is active.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn connection_range_preview_system(
+    mut commands: Commands,
+    hovering_query: Query<&Construction, With<UnderConstructionMarker>>,
+    added_hovering_query: Query<(), (With<UnderConstructionMarker>, Added<Construction>)>,
+    changed_placed_query: Query<(), (Without<UnderConstructionMarker>, Changed<Construction>)>,
+    mut removal_events: EventReader<RemovalEvent<Construction>>,
+    range_query: Query<Entity, With<ConnectionRangePreview>>,
+    construction_query: Query<&Construction>,
+    game_configuration: Res<GameConfiguration>,
+    construction_registry: Res<ConstructionRegistry>,
+) {
+    let hovering_construction = match hovering_query.iter().next() {
+        Some(hovering_construction) => hovering_construction,
+        None => {
+            for range_entity in range_query.iter() {
+                commands.entity(range_entity).despawn();
+            }
+            return;
+        }
+    };
+
+    let constructions_removed = removal_events.iter().count() > 0;
+    if added_hovering_query.is_empty()
+        && changed_placed_query.is_empty()
+        && !constructions_removed
+        && !game_configuration.is_changed()
+        && !construction_registry.is_changed()
+    {
+        return;
+    }
+
+    for range_entity in range_query.iter() {
+        commands.entity(range_entity).despawn();
+    }
+
+    let placed_constructions = construction_query
+        .iter()
+        .filter(|construction| construction.status != ConstructionStatus::Hovering);
+    for construction in placed_constructions {
+        let range_circle = Circle {
+            radius: logic::construction::max_connection_distance(
+                &game_configuration,
+                &construction_registry,
+                &construction.kind,
+                &hovering_construction.kind,
+            ),
+            center: Vec2::ZERO,
+        };
+        commands
+            .spawn_bundle(GeometryBuilder::build_as(
+                &range_circle,
+                DrawMode::Stroke(StrokeMode::new(RANGE_COLOR, 1.)),
+                Transform::from_translation(construction.location.extend(Z_VALUE)),
+            ))
+            .insert(ConnectionRangePreview);
+    }
+}
+
+fn preview_bundle(from_location: Vec2, to_location: Vec2, crosses_connection: bool) -> ShapeBundle {
+    let (color, dash_length) = if crosses_connection {
+        (PREVIEW_CROSSING_COLOR, DOT_LENGTH)
+    } else {
+        (PREVIEW_COLOR, DASH_LENGTH)
+    };
+
+    GeometryBuilder::build_as(
+        &dashed_line(from_location, to_location, dash_length),
+        DrawMode::Stroke(StrokeMode::new(color, 2.)),
+        Transform::from_translation(Vec3::Z * Z_VALUE),
+    )
+}
+
+/// Path of separate dashes from `from_location` to `to_location`
+fn dashed_line(from_location: Vec2, to_location: Vec2, dash_length: f32) -> Path {
+    let length = from_location.distance(to_location);
+    let direction = (to_location - from_location).normalize_or_zero();
+
+    let mut path_builder = PathBuilder::new();
+    let mut dash_start = 0.;
+    while dash_start < length {
+        let dash_end = f32::min(dash_start + dash_length, length);
+        path_builder.move_to(from_location + direction * dash_start);
+        path_builder.line_to(from_location + direction * dash_end);
+        dash_start = dash_end + DASH_GAP;
+    }
+    path_builder.build()
+}
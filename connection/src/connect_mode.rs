@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::ShapeBundle;
+use bevy_prototype_lyon::prelude::*;
+use bevy_prototype_lyon::shapes::Line;
+use model::{
+    connection::Connection,
+    construction::Construction,
+    construction_registry::ConstructionRegistry,
+    game::{GameEvent, GameMode},
+    game_configuration::GameConfiguration,
+};
+
+const Z_VALUE: f32 = 1.;
+const PICK_RADIUS: f32 = 10.;
+const CUT_DISTANCE: f32 = 5.;
+
+const PREVIEW_VALID_COLOR: Color = Color::CYAN;
+const PREVIEW_INVALID_COLOR: Color = Color::RED;
+
+/// Line that follows the cursor while dragging a new connection away from a construction
+#[derive(Component, Debug)]
+pub(crate) struct ConnectionPreview {
+    from: Entity,
+}
+
+pub(crate) fn connect_mode_on_mouse_press_system(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    construction_query: Query<(Entity, &Construction)>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    if *game_mode != GameMode::Connecting || !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let world_location = match logic::window_to_world(&windows, &camera_query) {
+        Some(world_location) => world_location,
+        None => return,
+    };
+
+    let from = logic::construction::construction_at(
+        construction_query.iter(),
+        world_location,
+        PICK_RADIUS,
+    );
+    match from.and_then(|from| construction_query.get(from).ok()) {
+        Some((from, construction)) => {
+            commands
+                .spawn_bundle(preview_bundle(construction.location, world_location, false))
+                .insert(ConnectionPreview { from });
+        }
+        None => game_event_writer.send(GameEvent::SwitchToGameMode(GameMode::Idle)),
+    }
+}
+
+/// Updates the preview line while dragging and requests the connection once the mouse button is
+/// released above another construction.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn connect_mode_on_mouse_drag_system(
+    mut commands: Commands,
+    preview_query: Query<(Entity, &ConnectionPreview)>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    construction_query: Query<(Entity, &Construction)>,
+    connection_query: Query<&Connection>,
+    game_configuration: Res<GameConfiguration>,
+    construction_registry: Res<ConstructionRegistry>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    for (preview_entity, preview) in preview_query.iter() {
+        let world_location = match logic::window_to_world(&windows, &camera_query) {
+            Some(world_location) => world_location,
+            None => continue,
+        };
+        let from_location = match construction_query.get(preview.from) {
+            Ok((_, construction)) => construction.location,
+            Err(_) => {
+                commands.entity(preview_entity).despawn();
+                continue;
+            }
+        };
+
+        // Snap the line to the construction below the cursor, if there is one
+        let to = logic::construction::construction_at(
+            construction_query.iter(),
+            world_location,
+            PICK_RADIUS,
+        )
+        .filter(|to| *to != preview.from);
+        let (to_location, valid) = match to.and_then(|to| construction_query.get(to).ok()) {
+            Some((to, construction)) => (
+                construction.location,
+                logic::construction::validate_connection(
+                    &game_configuration,
+                    &construction_registry,
+                    &construction_query,
+                    &connection_query,
+                    preview.from,
+                    to,
+                ),
+            ),
+            None => (world_location, false),
+        };
+
+        if mouse_buttons.just_released(MouseButton::Left) {
+            commands.entity(preview_entity).despawn();
+
+            let event = match to {
+                Some(to) if valid => GameEvent::ConnectConstructions(preview.from, to),
+                _ => GameEvent::SwitchToGameMode(GameMode::Idle),
+            };
+            game_event_writer.send(event);
+        } else {
+            commands
+                .entity(preview_entity)
+                .insert_bundle(preview_bundle(from_location, to_location, valid));
+        }
+    }
+}
+
+pub(crate) fn exit_connect_mode_system(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    preview_query: Query<Entity, With<ConnectionPreview>>,
+) {
+    if !game_mode.is_changed() {
+        return;
+    }
+
+    for preview_entity in preview_query.iter() {
+        commands.entity(preview_entity).despawn();
+    }
+}
+
+/// Requests to cut the connection below the cursor. Clicking anywhere else leaves the mode.
+pub(crate) fn disconnect_mode_on_mouse_click_system(
+    game_mode: Res<GameMode>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    construction_query: Query<(Entity, &Construction)>,
+    connection_query: Query<(Entity, &Connection)>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    if *game_mode != GameMode::Disconnecting || !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let world_location = match logic::window_to_world(&windows, &camera_query) {
+        Some(world_location) => world_location,
+        None => return,
+    };
+
+    let connection = logic::construction::connection_at(
+        connection_query.iter(),
+        &construction_query,
+        world_location,
+        CUT_DISTANCE,
+    );
+    match connection {
+        Some(connection) => game_event_writer.send(GameEvent::CutConnection(connection)),
+        None => game_event_writer.send(GameEvent::SwitchToGameMode(GameMode::Idle)),
+    }
+}
+
+fn preview_bundle(from_location: Vec2, to_location: Vec2, valid: bool) -> ShapeBundle {
+    let color = if valid {
+        PREVIEW_VALID_COLOR
+    } else {
+        PREVIEW_INVALID_COLOR
+    };
+
+    GeometryBuilder::build_as(
+        &Line(from_location, to_location),
+        DrawMode::Stroke(StrokeMode::new(color, 2.)),
+        Transform::from_translation(Vec3::Z * Z_VALUE),
+    )
+}
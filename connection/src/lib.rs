@@ -3,7 +3,8 @@ use bevy_prototype_lyon::prelude::*;
 use bevy_prototype_lyon::shapes::Line;
 use model::{connection::Connection, construction::Construction, RemovalEvent};
 
-mod energy_flow;
+mod build_preview;
+mod connect_mode;
 
 pub struct ConnectionShapePlugin;
 
@@ -12,10 +13,12 @@ impl Plugin for ConnectionShapePlugin {
         app.add_system(spawn_connection_shape_system)
             .add_system(update_connection_shape_system)
             .add_system_to_stage(CoreStage::PostUpdate, remove_connection_shape_system)
-            .add_system(on_construction_remove_system)
-            .add_event::<energy_flow::UpdateConnectionEnergyFlows>()
-            .add_system(energy_flow::emit_update_connection_energy_flows_system)
-            .add_system(energy_flow::on_update_connection_energy_flows_system);
+            .add_system(build_preview::build_connection_preview_system)
+            .add_system(build_preview::connection_range_preview_system)
+            .add_system(connect_mode::connect_mode_on_mouse_press_system)
+            .add_system(connect_mode::connect_mode_on_mouse_drag_system)
+            .add_system(connect_mode::exit_connect_mode_system)
+            .add_system(connect_mode::disconnect_mode_on_mouse_click_system);
     }
 }
 
@@ -135,30 +138,6 @@ fn remove_connection_shape_system(
     }
 }
 
-/// On construction remove, query all connections and remove all that were connected
-/// to the construction to be removed.
-fn on_construction_remove_system(
-    mut construction_removal_events: EventReader<RemovalEvent<Construction>>,
-    mut connection_event_writer: EventWriter<RemovalEvent<Connection>>,
-    connection_query: Query<(Entity, &Connection)>,
-) {
-    for event in construction_removal_events.iter() {
-        connection_query
-            .iter()
-            .filter(|(_, connection)| connection.connects_to(event.entity))
-            .for_each(|(connection_entity, connection)| {
-                debug!(
-                    "Removing {:?} because {:?} removed",
-                    connection, event.component
-                );
-                connection_event_writer.send(RemovalEvent {
-                    entity: connection_entity,
-                    component: connection.clone(),
-                });
-            });
-    }
-}
-
 fn spawn_connection_shape(
     commands: &mut Commands,
     location1: Vec2,
@@ -1,7 +1,5 @@
 use bevy::{input::mouse::MouseMotion, prelude::*};
 
-use model::collision::Collisions;
-
 use model::{
     construction::{Construction, ConstructionStatus, UnderConstructionMarker},
     game::{GameEvent, GameMode},
@@ -64,23 +62,19 @@ pub(crate) fn build_mode_on_mouse_move_system(
     }
 }
 
+/// Requests to build the hovering construction at its current location. Whether it can actually
+/// be built there is validated when the game event is processed.
 pub(crate) fn build_mode_on_mouse_click_system(
-    under_construction_query: Query<(&Construction, &Collisions), With<UnderConstructionMarker>>,
+    under_construction_query: Query<&Construction, With<UnderConstructionMarker>>,
     mouse_buttons: Res<Input<MouseButton>>,
     mut game_event_writer: EventWriter<GameEvent>,
 ) {
-    for (construction, collisions) in under_construction_query.iter() {
+    for construction in under_construction_query.iter() {
         if mouse_buttons.just_pressed(MouseButton::Left) {
-            if collisions.0.is_empty() {
-                game_event_writer.send(GameEvent::BuildConstruction(
-                    construction.location,
-                    construction.kind.clone(),
-                ));
-            } else {
-                // There are collisions, so the construction cannot be build here. Switch back to
-                // default game mode
-                game_event_writer.send(GameEvent::SwitchToGameMode(GameMode::Idle));
-            }
+            game_event_writer.send(GameEvent::BuildConstruction(
+                construction.location,
+                construction.kind.clone(),
+            ));
         }
     }
 }
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+use model::{
+    construction::Construction,
+    game::{GameEvent, GameMode},
+};
+
+use crate::SHAPE_RADIUS;
+
+/// Requests to demolish the construction below the cursor. Clicking anywhere else leaves the
+/// demolish mode.
+pub(crate) fn demolish_mode_on_mouse_click_system(
+    game_mode: Res<GameMode>,
+    construction_query: Query<(Entity, &Construction)>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    if *game_mode != GameMode::Demolishing || !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let world_location = match logic::window_to_world(&windows, &camera_query) {
+        Some(world_location) => world_location,
+        None => return,
+    };
+
+    let clicked_construction = logic::construction::construction_at(
+        construction_query.iter(),
+        world_location,
+        SHAPE_RADIUS,
+    );
+    match clicked_construction {
+        Some(entity) => game_event_writer.send(GameEvent::DemolishConstruction(entity)),
+        None => game_event_writer.send(GameEvent::SwitchToGameMode(GameMode::Idle)),
+    }
+}
@@ -1,12 +1,17 @@
 use bevy::prelude::*;
 use bevy_prototype_lyon::{prelude::*, shapes::Circle};
 use model::collision::Collisions;
-use model::construction::{ConstructionKind, ConstructionStatus};
+use model::construction::{ConstructionStatus, Idle};
+use model::construction_registry::ConstructionRegistry;
+use model::game::{BuildRejectionReason, PlacementRejection};
 use model::{construction::Construction, RemovalEvent};
 
 mod build_mode;
+mod demolish_mode;
+mod progress_ring;
 
 const Z_VALUE: f32 = 100.;
+const SHAPE_RADIUS: f32 = 10.;
 
 pub struct ConstructionShapePlugin;
 
@@ -16,10 +21,17 @@ impl Plugin for ConstructionShapePlugin {
             .add_system(spawn_construction_shape_system)
             .add_system(update_construction_shape_system)
             .add_system(remove_construction_shape_system)
+            .add_system(progress_ring::spawn_progress_ring_system)
+            .add_system(progress_ring::update_progress_ring_system)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                progress_ring::remove_progress_ring_system,
+            )
             .add_system(build_mode::enter_build_mode_system)
             .add_system(build_mode::exit_build_mode_system)
             .add_system(build_mode::build_mode_on_mouse_move_system)
-            .add_system(build_mode::build_mode_on_mouse_click_system);
+            .add_system(build_mode::build_mode_on_mouse_click_system)
+            .add_system(demolish_mode::demolish_mode_on_mouse_click_system);
     }
 }
 
@@ -33,16 +45,29 @@ struct ConstructionShape {
     construction: Entity,
 }
 
+type ConstructionAppearance<'a> = (
+    Option<&'a Collisions>,
+    Option<&'a Idle>,
+    Option<&'a PlacementRejection>,
+);
+
 fn spawn_construction_shape_system(
     mut commands: Commands,
-    query: Query<(Entity, &Construction, Option<&Collisions>), Added<Construction>>,
+    construction_registry: Res<ConstructionRegistry>,
+    query: Query<(Entity, &Construction, ConstructionAppearance), Added<Construction>>,
 ) {
-    for (entity, construction, collisions) in query.iter() {
+    for (entity, construction, (collisions, idle, placement_rejection)) in query.iter() {
         let construction_circle = Circle {
-            radius: 10.,
+            radius: SHAPE_RADIUS,
             center: Vec2::ZERO,
         };
-        let color = construction_color(construction, collisions);
+        let color = construction_color(
+            &construction_registry,
+            construction,
+            collisions,
+            idle.is_some(),
+            placement_rejection,
+        );
 
         let bundle = GeometryBuilder::build_as(
             &construction_circle,
@@ -67,35 +92,81 @@ fn spawn_construction_shape_system(
     }
 }
 
-fn construction_color(construction: &Construction, collisions: Option<&Collisions>) -> Color {
+fn construction_color(
+    construction_registry: &ConstructionRegistry,
+    construction: &Construction,
+    collisions: Option<&Collisions>,
+    idle: bool,
+    placement_rejection: Option<&PlacementRejection>,
+) -> Color {
     let has_collisions = collisions.map_or(false, |c| !c.0.is_empty());
+    let rejection_reason = placement_rejection.and_then(|rejection| rejection.0.as_ref());
     return if has_collisions && construction.status == ConstructionStatus::Hovering {
         let mut color = Color::RED;
         color.set_a(0.7);
         color
+    } else if let Some(reason) = rejection_reason {
+        let mut color = rejection_color(reason);
+        color.set_a(0.7);
+        color
+    } else if idle {
+        Color::DARK_GRAY
     } else {
-        match construction.kind {
-            ConstructionKind::Base => Color::GREEN,
-            ConstructionKind::Collector => Color::YELLOW,
-            ConstructionKind::Extractor => Color::BLUE,
+        let [red, green, blue, alpha] = construction_registry.color(&construction.kind);
+        let mut color = Color::rgba(red, green, blue, alpha);
+        if let ConstructionStatus::Building { .. } = construction.status {
+            color.set_a(0.5);
         }
+        color
     };
 }
 
+/// Each reason a placement is rejected for gets its own color, so the player can tell what to change
+fn rejection_color(reason: &BuildRejectionReason) -> Color {
+    match reason {
+        BuildRejectionReason::NotBuildable
+        | BuildRejectionReason::InfluenceOverlap
+        | BuildRejectionReason::ObstacleOverlap
+        | BuildRejectionReason::ConnectionOverlap => Color::RED,
+        BuildRejectionReason::InsufficientMaterial { .. } => Color::GRAY,
+        BuildRejectionReason::NoDeposit => Color::PURPLE,
+        BuildRejectionReason::OutOfNetworkReach => Color::ORANGE,
+    }
+}
+
 fn update_construction_shape_system(
     mut commands: Commands,
+    construction_registry: Res<ConstructionRegistry>,
     construction_query: Query<
-        (&ConstructionShapeRef, &Construction, &Collisions),
-        Changed<Construction>,
+        (
+            &ConstructionShapeRef,
+            &Construction,
+            &Collisions,
+            Option<&Idle>,
+            Option<&PlacementRejection>,
+        ),
+        Or<(
+            Changed<Construction>,
+            Added<Idle>,
+            Changed<PlacementRejection>,
+        )>,
     >,
     mut query: Query<&mut Transform>,
 ) {
-    for (shape_ref, construction, collisions) in construction_query.iter() {
+    for (shape_ref, construction, collisions, idle, placement_rejection) in
+        construction_query.iter()
+    {
         let query_result = query.get_mut(shape_ref.construction_shape);
         if let Ok(mut transform) = query_result {
             transform.translation = construction.location.extend(Z_VALUE);
 
-            let color = construction_color(construction, Some(collisions));
+            let color = construction_color(
+                &construction_registry,
+                construction,
+                Some(collisions),
+                idle.is_some(),
+                placement_rejection,
+            );
             commands
                 .entity(shape_ref.construction_shape)
                 .insert(DrawMode::Fill(FillMode::color(color)));
@@ -0,0 +1,145 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_prototype_lyon::entity::ShapeBundle;
+use bevy_prototype_lyon::prelude::*;
+use model::{
+    construction::{Construction, ConstructionStatus, ProductionCycle},
+    simulation::SimulationClock,
+    RemovalEvent,
+};
+
+const Z_VALUE: f32 = 101.;
+const RING_RADIUS: f32 = 14.;
+const RING_WIDTH: f32 = 3.;
+const BUILDING_RING_COLOR: Color = Color::WHITE;
+const PRODUCTION_RING_COLOR: Color = Color::ORANGE;
+
+/// The simulation only updates the progress once per tick. In between, the ring is interpolated
+/// from the progress of the previous tick to the one of the last tick.
+#[derive(Component, Debug)]
+pub(crate) struct ProgressRing {
+    construction: Entity,
+    /// Tick in which `progress` was taken
+    tick: u64,
+    previous_progress: f32,
+    progress: f32,
+    shown_progress: f32,
+}
+
+pub(crate) fn spawn_progress_ring_system(
+    mut commands: Commands,
+    clock: Res<SimulationClock>,
+    query: Query<(Entity, &Construction, Option<&ProductionCycle>), Added<Construction>>,
+) {
+    for (entity, construction, production_cycle) in query.iter() {
+        let (progress, color) = construction_progress(construction, production_cycle);
+        commands
+            .spawn_bundle(progress_ring_bundle(construction.location, progress, color))
+            .insert(ProgressRing {
+                construction: entity,
+                tick: clock.tick,
+                previous_progress: progress,
+                progress,
+                shown_progress: progress,
+            });
+    }
+}
+
+pub(crate) fn update_progress_ring_system(
+    mut commands: Commands,
+    clock: Res<SimulationClock>,
+    mut ring_query: Query<(Entity, &mut ProgressRing)>,
+    construction_query: Query<(
+        &Construction,
+        ChangeTrackers<Construction>,
+        Option<&ProductionCycle>,
+    )>,
+) {
+    for (ring_entity, mut ring) in ring_query.iter_mut() {
+        let (construction, construction_tracker, production_cycle) =
+            match construction_query.get(ring.construction) {
+                Ok(construction) => construction,
+                Err(_) => continue,
+            };
+
+        let (progress, color) = construction_progress(construction, production_cycle);
+        if ring.tick != clock.tick {
+            ring.tick = clock.tick;
+            ring.previous_progress = ring.progress;
+            ring.progress = progress;
+        }
+
+        // A completed production cycle starts over, which is not interpolated backwards
+        let shown_progress = if ring.progress < ring.previous_progress {
+            ring.progress
+        } else {
+            ring.previous_progress
+                + (ring.progress - ring.previous_progress) * clock.overstep_fraction()
+        };
+        if shown_progress != ring.shown_progress || construction_tracker.is_changed() {
+            ring.shown_progress = shown_progress;
+            commands
+                .entity(ring_entity)
+                .insert_bundle(progress_ring_bundle(
+                    construction.location,
+                    shown_progress,
+                    color,
+                ));
+        }
+    }
+}
+
+pub(crate) fn remove_progress_ring_system(
+    mut commands: Commands,
+    mut removal_events: EventReader<RemovalEvent<Construction>>,
+    query: Query<(Entity, &ProgressRing)>,
+) {
+    for event in removal_events.iter() {
+        query
+            .iter()
+            .filter(|(_, progress_ring)| progress_ring.construction == event.entity)
+            .for_each(|(ring_entity, _)| {
+                debug!(
+                    "Despawning ProgressRing {:?} of Construction {:?}",
+                    ring_entity, event.entity
+                );
+                commands.entity(ring_entity).despawn();
+            });
+    }
+}
+
+/// Progress that is shown around a construction: Construction sites show how far they are built,
+/// operating constructions the cycle of their material production. Others show no progress.
+fn construction_progress(
+    construction: &Construction,
+    production_cycle: Option<&ProductionCycle>,
+) -> (f32, Color) {
+    match (&construction.status, production_cycle) {
+        (ConstructionStatus::Building { progress }, _) => (*progress, BUILDING_RING_COLOR),
+        (ConstructionStatus::Operating, Some(production_cycle)) => {
+            (production_cycle.progress, PRODUCTION_RING_COLOR)
+        }
+        _ => (0., Color::NONE),
+    }
+}
+
+fn progress_ring_bundle(location: Vec2, progress: f32, color: Color) -> ShapeBundle {
+    let mut path_builder = PathBuilder::new();
+    if progress > 0. {
+        // Start at the top and fill the ring clockwise
+        path_builder.move_to(Vec2::new(0., RING_RADIUS));
+        path_builder.arc(
+            Vec2::ZERO,
+            Vec2::splat(RING_RADIUS),
+            -progress.min(1.) * TAU,
+            0.,
+        );
+    }
+
+    GeometryBuilder::build_as(
+        &path_builder.build(),
+        DrawMode::Stroke(StrokeMode::new(color, RING_WIDTH)),
+        Transform::from_translation(location.extend(Z_VALUE)),
+    )
+}
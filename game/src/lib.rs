@@ -1,29 +1,66 @@
 use bevy::prelude::*;
+use camera::CameraPlugin;
 use collision::CollisionPlugin;
 use connection::ConnectionShapePlugin;
 use construction::ConstructionShapePlugin;
 use hud::HudPlugin;
 use influence::InfluenceShapePlugin;
 use logic::LogicPlugin;
+use map::MapPlugin;
+use model::recording::GameRecording;
+use model::simulation::SimulationClock;
 use model::ModelPlugin;
 use resources::ResourcesPlugin;
 
+/// The complete game, to be added next to `DefaultPlugins`
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(SimulationPlugin)
+            .add_plugin(PresentationPlugin);
+    }
+}
+
+/// The rules of the game, without anything that is drawn. It only needs `MinimalPlugins`, so
+/// tests and tools can step the game without a window or a GPU.
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(ModelPlugin)
-            .add_startup_system(init_camera_system)
             .add_plugin(ResourcesPlugin)
+            .add_plugin(CollisionPlugin)
+            .add_plugin(LogicPlugin);
+    }
+}
+
+/// Shapes, camera and HUD that show the simulation and turn the player input into game events
+pub struct PresentationPlugin;
+
+impl Plugin for PresentationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(CameraPlugin)
+            .add_plugin(MapPlugin)
             .add_plugin(ConstructionShapePlugin)
             .add_plugin(InfluenceShapePlugin)
             .add_plugin(ConnectionShapePlugin)
-            .add_plugin(HudPlugin)
-            .add_plugin(CollisionPlugin)
-            .add_plugin(LogicPlugin);
+            .add_plugin(HudPlugin);
     }
 }
 
-fn init_camera_system(mut commands: Commands) {
-    commands.spawn_bundle(Camera2dBundle::default());
+/// Replays a recording without presentation and as fast as possible, then compares the state it
+/// ended in to the one of the recorded game
+pub fn verify_recording(recording: &GameRecording) -> Result<(), String> {
+    let ticks_per_second = recording.snapshot.game_configuration.ticks_per_second;
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(SimulationClock::stepped(ticks_per_second))
+        .add_plugin(SimulationPlugin);
+
+    logic::replay::start_replay(&mut app.world, recording);
+    while !logic::replay::is_replay_finished(&app.world) {
+        app.update();
+    }
+    logic::replay::verify_replay(&mut app.world)
 }
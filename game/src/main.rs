@@ -0,0 +1,213 @@
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::app::AppExit;
+use bevy::asset::AssetServerSettings;
+use bevy::ecs::event::Events;
+use bevy::prelude::*;
+use game::GamePlugin;
+use logic::map_generation::{self, GeneratedMap};
+use logic::replay::{self, GameEventReplay};
+use logic::scenario::{self, ScenarioPlugin};
+use model::configuration_file;
+use model::recording::GameRecording;
+use model::terrain::Terrain;
+
+const ASSET_FOLDER: &str = "../assets";
+const TERRAIN_FILE: &str = "terrain.ron";
+
+const USAGE: &str = "Usage: game [--seed <number> | --scenario <file>] \
+    [--record <file> | --replay <file> | --verify <file>]";
+
+#[derive(Default)]
+struct Arguments {
+    seed: Option<u64>,
+    scenario: Option<PathBuf>,
+    record: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    verify: Option<PathBuf>,
+}
+
+/// File the game is recorded to until the app exits
+struct RecordingFile(PathBuf);
+
+fn main() {
+    let arguments = match parse_arguments(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(path) = &arguments.verify {
+        std::process::exit(verify(path));
+    }
+    let recording = arguments
+        .replay
+        .as_deref()
+        .map(|path| replay::load_recording(path).unwrap_or_else(|error| exit_with_error(&error)));
+    let scenario = arguments
+        .scenario
+        .as_deref()
+        .map(|path| scenario::load_scenario(path).unwrap_or_else(|error| exit_with_error(&error)));
+
+    let mut app = App::new();
+    app.insert_resource(Msaa { samples: 4 })
+        .insert_resource(AssetServerSettings {
+            asset_folder: ASSET_FOLDER.to_string(),
+            watch_for_changes: false,
+        });
+
+    // A scenario brings its own layout, otherwise a map is generated
+    match scenario {
+        Some(scenario) => {
+            app.add_plugins(DefaultPlugins)
+                .add_plugin(GamePlugin)
+                .add_plugin(ScenarioPlugin { scenario });
+        }
+        None => {
+            let seed = arguments.seed.unwrap_or_else(random_seed);
+            // The map is made of the regions of the terrain file
+            let terrain: Terrain =
+                configuration_file::load_ron_file(&Path::new(ASSET_FOLDER).join(TERRAIN_FILE))
+                    .unwrap_or_else(|error| exit_with_error(&error));
+            let generated_map = map_generation::generate_map(seed, &terrain.regions);
+
+            app.insert_resource(generated_map.terrain.clone())
+                .insert_resource(generated_map)
+                .add_plugins(DefaultPlugins)
+                .add_plugin(GamePlugin)
+                .add_startup_system(init_game_system);
+            // Logging is only set up by the default plugins
+            info!("Generated map with seed {}", seed);
+        }
+    }
+
+    // Recording and replay start once the startup systems spawned the map
+    if let Some(path) = arguments.record {
+        app.insert_resource(RecordingFile(path))
+            .add_startup_system_to_stage(
+                StartupStage::PostStartup,
+                start_recording_system.exclusive_system(),
+            )
+            .add_system_to_stage(CoreStage::Last, save_recording_system.exclusive_system());
+    }
+    if let Some(recording) = recording {
+        app.insert_resource(recording)
+            .add_startup_system_to_stage(
+                StartupStage::PostStartup,
+                start_replay_system.exclusive_system(),
+            )
+            .add_system(verify_replay_system.exclusive_system());
+    }
+    app.run();
+}
+
+fn exit_with_error(error: &str) -> ! {
+    eprintln!("{}", error);
+    std::process::exit(2);
+}
+
+fn init_game_system(mut commands: Commands, generated_map: Res<GeneratedMap>) {
+    map_generation::spawn_generated_map(&mut commands, &generated_map);
+}
+
+fn start_recording_system(world: &mut World) {
+    replay::start_recording(world);
+}
+
+/// Saves the recording when the app is about to exit, e.g. because the window was closed
+fn save_recording_system(world: &mut World) {
+    if world.resource::<Events<AppExit>>().is_empty() {
+        return;
+    }
+    let path = match world.remove_resource::<RecordingFile>() {
+        Some(RecordingFile(path)) => path,
+        None => return,
+    };
+    match replay::save_recording(world, &path) {
+        Ok(()) => info!("Saved recording to {}", path.display()),
+        Err(error) => error!("Cannot save recording to {}: {}", path.display(), error),
+    }
+}
+
+fn start_replay_system(world: &mut World) {
+    let recording = world.remove_resource::<GameRecording>().unwrap();
+    replay::start_replay(world, &recording);
+}
+
+/// Reports whether the replay ended in the recorded state. The game goes on afterwards.
+fn verify_replay_system(world: &mut World) {
+    if !replay::is_replay_finished(world) {
+        return;
+    }
+    match replay::verify_replay(world) {
+        Ok(()) => info!("Replay ended in the recorded state"),
+        Err(error) => error!("{}", error),
+    }
+    world.remove_resource::<GameEventReplay>();
+}
+
+/// Replays a recording without a window and returns the exit code
+fn verify(path: &Path) -> i32 {
+    let result =
+        replay::load_recording(path).and_then(|recording| game::verify_recording(&recording));
+    match result {
+        Ok(()) => {
+            println!("Replay of {} ended in the recorded state", path.display());
+            0
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            1
+        }
+    }
+}
+
+fn parse_arguments(mut args: impl Iterator<Item = String>) -> Result<Arguments, String> {
+    let mut arguments = Arguments::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = args.next().ok_or("Missing value for --seed")?;
+                let parsed = value
+                    .parse()
+                    .map_err(|_| format!("Invalid seed '{}', expected a number", value))?;
+                arguments.seed = Some(parsed);
+            }
+            "--scenario" => {
+                let value = args.next().ok_or("Missing file for --scenario")?;
+                arguments.scenario = Some(value.into());
+            }
+            "--record" => {
+                let value = args.next().ok_or("Missing file for --record")?;
+                arguments.record = Some(value.into());
+            }
+            "--replay" => {
+                let value = args.next().ok_or("Missing file for --replay")?;
+                arguments.replay = Some(value.into());
+            }
+            "--verify" => {
+                let value = args.next().ok_or("Missing file for --verify")?;
+                arguments.verify = Some(value.into());
+            }
+            _ => return Err(format!("Unknown argument '{}'", arg)),
+        }
+    }
+
+    if arguments.seed.is_some() && arguments.scenario.is_some() {
+        return Err("A scenario cannot be combined with a map seed".into());
+    }
+    let modes = [&arguments.record, &arguments.replay, &arguments.verify];
+    if modes.iter().filter(|mode| mode.is_some()).count() > 1 {
+        return Err("Only one of --record, --replay and --verify can be given".into());
+    }
+    Ok(arguments)
+}
+
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
@@ -0,0 +1,135 @@
+use bevy::prelude::*;
+use model::{
+    connection::Connection,
+    construction::{Construction, UnderConstructionMarker},
+    construction_registry::ConstructionRegistry,
+    game::PlacementRejection,
+    game_configuration::GameConfiguration,
+    resources::PlayerResources,
+    terrain::Terrain,
+};
+
+/// Distance of the preview text to the cursor, in screen pixels
+const CURSOR_OFFSET: f32 = 20.;
+
+#[derive(Component)]
+pub(crate) struct BuildPreviewHud;
+
+pub(crate) fn create_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/OpenSans/OpenSans.ttf"),
+                    font_size: 20.,
+                    color: Color::WHITE,
+                },
+            ),
+            ..default()
+        })
+        .insert(BuildPreviewHud);
+}
+
+/// Shows next to the cursor what the construction in build mode would produce at its current
+/// location and what it costs including the connections the player can afford, or why it cannot
+/// be built there.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_hud(
+    mut hud_query: Query<(&mut Text, &mut Style), With<BuildPreviewHud>>,
+    preview_query: Query<
+        (&Construction, Option<&PlacementRejection>),
+        With<UnderConstructionMarker>,
+    >,
+    construction_query: Query<(Entity, &Construction)>,
+    connection_query: Query<&Connection>,
+    windows: Res<Windows>,
+    game_configuration: Res<GameConfiguration>,
+    construction_registry: Res<ConstructionRegistry>,
+    player_resources: Res<PlayerResources>,
+    terrain: Res<Terrain>,
+) {
+    let cursor_position = windows
+        .get_primary()
+        .and_then(|window| window.cursor_position());
+
+    for (mut hud, mut style) in hud_query.iter_mut() {
+        hud.sections[0].value.clear();
+
+        let ((construction, placement_rejection), cursor_position) =
+            match (preview_query.iter().next(), cursor_position) {
+                (Some(preview), Some(cursor_position)) => (preview, cursor_position),
+                _ => continue,
+            };
+
+        let region_name = terrain
+            .region_at(construction.location)
+            .map_or("open terrain", |region| region.name.as_str());
+        let name = construction_registry
+            .get(&construction.kind)
+            .map_or(construction.kind.0.as_str(), |definition| {
+                definition.name.as_str()
+            });
+        let mut preview = format!("{} on {}", name, region_name);
+
+        let material_output = logic::terrain::effective_material_output(
+            &construction_registry,
+            &terrain,
+            construction,
+        );
+        if material_output > 0. {
+            preview += &format!("\n+{:.2} Material/s", material_output);
+        }
+
+        let energy_output =
+            logic::terrain::effective_energy_output(&construction_registry, &terrain, construction);
+        if energy_output > 0. {
+            preview += &format!("\n+{:.1} Energy", energy_output);
+        }
+
+        // Charged the same way as `build_construction` does
+        let material_build_demand = construction_registry.material_build_demand(&construction.kind);
+        let planned_connections = logic::construction::planned_connections(
+            &game_configuration,
+            &construction_registry,
+            &construction_query.iter().collect(),
+            &connection_query.iter().collect(),
+            &construction.location,
+            &construction.kind,
+        );
+        let (affordable_connections, unaffordable_connections) =
+            logic::construction::affordable_connections(
+                planned_connections,
+                player_resources.material_available - material_build_demand,
+            );
+        let connections_material_cost: f64 = affordable_connections
+            .iter()
+            .map(|candidate| candidate.material_cost)
+            .sum();
+        preview += &format!(
+            "\nCosts {:.0} Material",
+            material_build_demand + connections_material_cost
+        );
+        if !unaffordable_connections.is_empty() {
+            preview += &format!(
+                "\n{} connection(s) skipped, not enough Material",
+                unaffordable_connections.len()
+            );
+        }
+
+        if let Some(reason) = placement_rejection.and_then(|rejection| rejection.0.as_ref()) {
+            preview += &format!("\n{}", reason);
+        }
+
+        hud.sections[0].value = preview;
+        style.position = UiRect {
+            left: Val::Px(cursor_position.x + CURSOR_OFFSET),
+            bottom: Val::Px(cursor_position.y + CURSOR_OFFSET),
+            ..default()
+        };
+    }
+}
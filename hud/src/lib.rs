@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 
+mod build_preview_hud;
 mod menu_hud;
+mod message_hud;
 mod resources_hud;
+mod save_game;
 
 pub struct HudPlugin;
 
@@ -10,6 +13,11 @@ impl Plugin for HudPlugin {
         app.add_startup_system(resources_hud::create_hud)
             .add_system(resources_hud::update_hud)
             .add_startup_system(menu_hud::create_hud)
-            .add_system(menu_hud::menu_hud_system);
+            .add_system(menu_hud::menu_hud_system)
+            .add_startup_system(message_hud::create_hud)
+            .add_system(message_hud::update_hud)
+            .add_startup_system(build_preview_hud::create_hud)
+            .add_system(build_preview_hud::update_hud)
+            .add_system(save_game::save_game_hotkey_system.exclusive_system());
     }
 }
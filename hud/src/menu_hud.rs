@@ -1,36 +1,67 @@
 use bevy::prelude::*;
 use model::{
     construction::ConstructionKind,
+    construction_registry::ConstructionRegistry,
     game::{GameEvent, GameMode},
 };
 
 #[derive(Component, Debug)]
 pub(crate) struct BuildButton(ConstructionKind);
 
-pub(crate) fn create_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Component, Debug)]
+pub(crate) struct ModeButton(GameMode);
+
+pub(crate) fn create_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    construction_registry: Res<ConstructionRegistry>,
+) {
     commands
         .spawn_bundle(elements::root_node_bundle())
         .with_children(|parent| {
             parent.spawn_bundle(elements::button_text_bundle("Build:", &asset_server));
 
+            for definition in construction_registry
+                .iter()
+                .filter(|definition| definition.buildable)
+            {
+                parent
+                    .spawn_bundle(elements::button_bundle())
+                    .insert(BuildButton(definition.kind.clone()))
+                    .with_children(|parent| {
+                        parent.spawn_bundle(elements::button_text_bundle(
+                            &definition.name,
+                            &asset_server,
+                        ));
+                    });
+            }
+
             parent
                 .spawn_bundle(elements::button_bundle())
-                .insert(BuildButton(ConstructionKind::Extractor))
+                .insert(ModeButton(GameMode::Connecting))
                 .with_children(|parent| {
-                    parent.spawn_bundle(elements::button_text_bundle("Extractor", &asset_server));
+                    parent.spawn_bundle(elements::button_text_bundle("Connect", &asset_server));
                 });
 
             parent
                 .spawn_bundle(elements::button_bundle())
-                .insert(BuildButton(ConstructionKind::Collector))
+                .insert(ModeButton(GameMode::Disconnecting))
                 .with_children(|parent| {
-                    parent.spawn_bundle(elements::button_text_bundle("Collector", &asset_server));
+                    parent.spawn_bundle(elements::button_text_bundle("Cut", &asset_server));
+                });
+
+            parent
+                .spawn_bundle(elements::button_bundle())
+                .insert(ModeButton(GameMode::Demolishing))
+                .with_children(|parent| {
+                    parent.spawn_bundle(elements::button_text_bundle("Demolish", &asset_server));
                 });
         });
 }
 
 pub(crate) fn menu_hud_system(
     mut interaction_query: Query<(&Interaction, &BuildButton), Changed<Interaction>>,
+    mode_interaction_query: Query<(&Interaction, &ModeButton), Changed<Interaction>>,
     mut game_event_writer: EventWriter<GameEvent>,
 ) {
     for (interaction, build_button) in interaction_query.iter_mut() {
@@ -39,6 +70,12 @@ pub(crate) fn menu_hud_system(
             game_event_writer.send(GameEvent::SwitchToGameMode(target_mode));
         }
     }
+
+    for (interaction, mode_button) in mode_interaction_query.iter() {
+        if interaction == &Interaction::Clicked {
+            game_event_writer.send(GameEvent::SwitchToGameMode(mode_button.0.clone()));
+        }
+    }
 }
 
 mod elements {
@@ -47,7 +84,7 @@ mod elements {
     pub(crate) fn root_node_bundle() -> NodeBundle {
         NodeBundle {
             style: Style {
-                size: Size::new(Val::Percent(40.), Val::Px(40.)),
+                size: Size::new(Val::Percent(70.), Val::Px(40.)),
                 padding: UiRect::all(Val::Px(5.)),
                 justify_content: JustifyContent::SpaceBetween,
                 align_items: AlignItems::Stretch,
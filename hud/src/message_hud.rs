@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use model::game::GameEvent;
+
+const MESSAGE_DURATION_SECS: f32 = 3.;
+
+#[derive(Component)]
+pub(crate) struct MessageHud {
+    timer: Timer,
+}
+
+pub(crate) fn create_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Px(10.),
+                    left: Val::Px(10.),
+                    ..default()
+                },
+                ..default()
+            },
+            text: Text::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/OpenSans/OpenSans.ttf"),
+                    font_size: 25.,
+                    color: Color::ORANGE_RED,
+                },
+            ),
+            ..default()
+        })
+        .insert(MessageHud {
+            timer: Timer::from_seconds(MESSAGE_DURATION_SECS, false),
+        });
+}
+
+/// Shows why the last build request was rejected for a few seconds.
+pub(crate) fn update_hud(
+    time: Res<Time>,
+    mut game_events: EventReader<GameEvent>,
+    mut hud_query: Query<(&mut Text, &mut MessageHud)>,
+) {
+    let rejection_reason = game_events
+        .iter()
+        .filter_map(|event| match event {
+            GameEvent::BuildRejected { reason, .. } => Some(reason),
+            _ => None,
+        })
+        .last();
+
+    for (mut hud, mut message_hud) in hud_query.iter_mut() {
+        if let Some(reason) = rejection_reason {
+            hud.sections[0].value = reason.to_string();
+            message_hud.timer.reset();
+        }
+
+        if message_hud.timer.tick(time.delta()).just_finished() {
+            hud.sections[0].value.clear();
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use model::resources::PlayerResources;
+use model::{energy_network::EnergyNetworks, resources::PlayerResources};
 
 #[derive(Component)]
 pub(crate) struct ResourcesHud;
@@ -32,6 +32,7 @@ pub(crate) fn create_hud(mut commands: Commands, asset_server: Res<AssetServer>)
 pub(crate) fn update_hud(
     mut hud_query: Query<&mut Text, With<ResourcesHud>>,
     resources: Res<PlayerResources>,
+    energy_networks: Res<EnergyNetworks>,
 ) {
     for mut hud in hud_query.iter_mut() {
         hud.sections[0].value = format!(
@@ -42,5 +43,14 @@ pub(crate) fn update_hud(
             resources.energy_available,
             resources.energy_need / resources.energy_available * 100.
         );
+
+        let starved_satisfactions: Vec<_> = energy_networks
+            .starved()
+            .map(|network| format!("{:.0}%", network.satisfaction() * 100.))
+            .collect();
+        if !starved_satisfactions.is_empty() {
+            hud.sections[0].value +=
+                &format!("\nStarved networks: {}", starved_satisfactions.join(", "));
+        }
     }
 }
@@ -0,0 +1,29 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+
+/// Save game in the working directory, there is a single slot only
+const SAVE_GAME_FILE: &str = "savegame.ron";
+const SAVE_KEY: KeyCode = KeyCode::F5;
+const LOAD_KEY: KeyCode = KeyCode::F9;
+
+/// Saves the game on F5 and loads the saved game on F9. Needs the whole world, as loading replaces
+/// all constructions and connections.
+pub(crate) fn save_game_hotkey_system(world: &mut World) {
+    let keyboard = world.resource::<Input<KeyCode>>();
+    let save = keyboard.just_pressed(SAVE_KEY);
+    let load = keyboard.just_pressed(LOAD_KEY);
+
+    let path = Path::new(SAVE_GAME_FILE);
+    if save {
+        match logic::snapshot::save_game(world, path) {
+            Ok(()) => info!("Saved the game to {}", path.display()),
+            Err(error) => error!("{}", error),
+        }
+    } else if load {
+        match logic::snapshot::load_game(world, path) {
+            Ok(()) => info!("Loaded the game from {}", path.display()),
+            Err(error) => error!("{}", error),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use bevy_prototype_lyon::{prelude::*, shapes::Circle};
-use model::game_configuration::GameConfiguration;
+use model::construction_registry::ConstructionRegistry;
 use model::{construction::Construction, RemovalEvent};
 
 const Z_VALUE: f32 = 90.;
@@ -27,12 +27,12 @@ struct InfluenceShape {
 
 fn spawn_influence_shape_system(
     mut commands: Commands,
-    game_configuration: Res<GameConfiguration>,
+    construction_registry: Res<ConstructionRegistry>,
     query: Query<(Entity, &Construction), Added<Construction>>,
 ) {
     for (entity, construction) in query.iter() {
         let construction_circle = Circle {
-            radius: game_configuration.influence_radius(&construction.kind),
+            radius: construction_registry.influence_radius(&construction.kind),
             center: Vec2::ZERO,
         };
         let color = Color::Rgba {
@@ -1,73 +1,168 @@
+use crate::energy_network::find_energy_networks;
+use crate::terrain::effective_energy_output;
+use crate::utils::min_cost_flow::FlowNetwork;
 use bevy::prelude::*;
 use model::connection::Connection;
 use model::construction::Construction;
+use model::construction_registry::ConstructionRegistry;
 use model::game_configuration::GameConfiguration;
+use model::terrain::Terrain;
 use std::collections::HashMap;
 
+/// Distributes the energy of all producing constructions to the consuming ones by solving a
+/// min-cost max-flow problem over the construction graph. Each connection carries at most
+/// `GameConfiguration::connection_capacity`, and routes with fewer hops are preferred.
+///
+/// If supply and demand of a network do not match, every producer first offers only its
+/// proportional share and every consumer first asks for its proportional share. Spare output is
+/// only used when a bottleneck prevents another producer from delivering its share.
 pub fn assign_energy_flows(
     game_configuration: &GameConfiguration,
+    construction_registry: &ConstructionRegistry,
+    terrain: &Terrain,
     constructions: &Vec<(Entity, &Construction)>,
     connections: &Vec<&Connection>,
 ) -> HashMap<(Entity, Entity), f64> {
-    let to_fn =
-        |construction: &Construction| game_configuration.energy_input(&construction.kind) > 0.;
+    // Supply and demand are balanced within each network, as energy cannot flow between them
+    let energy_networks =
+        find_energy_networks(construction_registry, terrain, constructions, connections);
+    let entity_to_factors_map: HashMap<Entity, (f64, f64)> = energy_networks
+        .iter()
+        .flat_map(|network| {
+            let supply_factor = f64::min(1., network.energy_demand / network.energy_supply);
+            let demand_factor = network.satisfaction();
+            network
+                .constructions
+                .iter()
+                .map(move |entity| (*entity, (supply_factor, demand_factor)))
+        })
+        .collect();
 
-    let (total_energy_availability, total_energy_demand) =
-        constructions
-            .iter()
-            .copied()
-            .fold((0., 0.), |acc, (_, construction)| {
-                (
-                    acc.0 + game_configuration.energy_output(&construction.kind),
-                    acc.1 + game_configuration.energy_input(&construction.kind),
-                )
-            });
-    let energy_factor = {
-        let factor = total_energy_demand / total_energy_availability;
-        if factor > 1. {
-            1.
-        } else {
-            factor
+    let entity_to_node_map: HashMap<Entity, usize> = constructions
+        .iter()
+        .enumerate()
+        .map(|(node, (entity, _))| (*entity, node))
+        .collect();
+    let source = constructions.len();
+    let sink = source + 1;
+    // Any route through the network is cheaper than using spare supply or demand
+    let spare_cost = 2. * (sink + 1) as f64;
+
+    let mut flow_network = FlowNetwork::new(sink + 1);
+    for (node, (entity, construction)) in constructions.iter().enumerate() {
+        let (supply_factor, demand_factor) = entity_to_factors_map[entity];
+
+        let energy_production =
+            effective_energy_output(construction_registry, terrain, construction);
+        if energy_production > 0. {
+            let share = supply_factor * energy_production;
+            flow_network.add_arc(source, node, share, 0.);
+            if energy_production > share {
+                flow_network.add_arc(source, node, energy_production - share, spare_cost);
+            }
         }
-    };
 
-    let entity_construction_map: HashMap<Entity, &Construction> =
-        constructions.iter().copied().collect();
-    let mut connection_flows: HashMap<(Entity, Entity), f64> = HashMap::new();
+        let energy_demand = construction_registry.construction_energy_input(construction);
+        if energy_demand > 0. {
+            let share = demand_factor * energy_demand;
+            flow_network.add_arc(node, sink, share, 0.);
+            if energy_demand > share {
+                flow_network.add_arc(node, sink, energy_demand - share, spare_cost);
+            }
+        }
+    }
 
-    let energy_producing_constructions = constructions
+    // Arcs are added in the order of the nodes rather than of the entities, so that the flow
+    // network does not depend on entity ids
+    let mut connection_nodes: Vec<_> = connections
         .iter()
         .copied()
-        .filter(|(_, c)| game_configuration.energy_output(&c.kind) > 0.);
-    for (producing_entity, producing_construction) in energy_producing_constructions {
-        let paths = crate::utils::routing::shortest_paths_from(
-            constructions,
-            connections,
-            producing_entity,
-            &to_fn,
-        );
+        .filter_map(|connection| {
+            let node0_opt = entity_to_node_map.get(&connection.between().0);
+            let node1_opt = entity_to_node_map.get(&connection.between().1);
+
+            if let (Some(node0), Some(node1)) = (node0_opt, node1_opt) {
+                Some((usize::min(*node0, *node1), usize::max(*node0, *node1)))
+            } else {
+                warn!("Connection {:?} links between two constructions, but at least one was not found: {:?}. Will ignore connection (and missing construction) in energy flow assigment.", connection, (node0_opt, node1_opt));
+                None
+            }
+        })
+        .collect();
+    connection_nodes.sort();
 
-        for path in paths {
-            let consuming_entity = path[0];
-            let consuming_construction = entity_construction_map.get(&consuming_entity).unwrap();
+    let connection_arcs: Vec<_> = connection_nodes
+        .into_iter()
+        .map(|(node0, node1)| {
+            let capacity = game_configuration.connection_capacity;
+            let forward_arc = flow_network.add_arc(node0, node1, capacity, 1.);
+            let backward_arc = flow_network.add_arc(node1, node0, capacity, 1.);
+            let entities = (constructions[node0].0, constructions[node1].0);
+            (entities, forward_arc, backward_arc)
+        })
+        .collect();
 
-            // Calculate the energy flowing from `producing_construction` to `consuming_construction`
-            let energy_production = game_configuration.energy_output(&producing_construction.kind);
-            let energy_demand = game_configuration.energy_input(&consuming_construction.kind);
-            let energy_flow: f64 =
-                energy_factor * energy_production * energy_demand / total_energy_demand;
+    flow_network.min_cost_max_flow(source, sink);
 
-            for entities in path.windows(2) {
-                *connection_flows
-                    .entry((entities[1], entities[0]))
-                    .or_insert(0.) += energy_flow;
-            }
-        }
+    let mut connection_flows: HashMap<(Entity, Entity), f64> = HashMap::new();
+    for (entities, forward_arc, backward_arc) in connection_arcs {
+        *connection_flows.entry(entities).or_insert(0.) +=
+            flow_network.flow(forward_arc) - flow_network.flow(backward_arc);
     }
 
     normalize_energy_flows(connection_flows)
 }
 
+/// Calculates for each energy consuming construction the fraction of its demand that is covered
+/// by the given (normalized) energy flows. Constructions without demand are not included.
+pub fn energy_satisfactions(
+    construction_registry: &ConstructionRegistry,
+    terrain: &Terrain,
+    constructions: &Vec<(Entity, &Construction)>,
+    energy_flows: &HashMap<(Entity, Entity), f64>,
+) -> HashMap<Entity, f64> {
+    // Flows are summed up in the order of the constructions, as the iteration order of a HashMap
+    // changes from run to run and floating point addition is not associative
+    let entity_to_index_map: HashMap<Entity, usize> = constructions
+        .iter()
+        .enumerate()
+        .map(|(index, (entity, _))| (*entity, index))
+        .collect();
+    let mut sorted_energy_flows: Vec<_> = energy_flows.iter().collect();
+    sorted_energy_flows.sort_by_key(|((entity1, entity2), _)| {
+        let index1 = entity_to_index_map
+            .get(entity1)
+            .copied()
+            .unwrap_or(usize::MAX);
+        let index2 = entity_to_index_map
+            .get(entity2)
+            .copied()
+            .unwrap_or(usize::MAX);
+        (usize::min(index1, index2), usize::max(index1, index2))
+    });
+
+    let mut net_inflows: HashMap<Entity, f64> = HashMap::new();
+    for ((from_entity, to_entity), energy_flow) in sorted_energy_flows {
+        *net_inflows.entry(*to_entity).or_insert(0.) += energy_flow;
+        *net_inflows.entry(*from_entity).or_insert(0.) -= energy_flow;
+    }
+
+    constructions
+        .iter()
+        .filter_map(|(entity, construction)| {
+            let energy_demand = construction_registry.construction_energy_input(construction);
+            if energy_demand <= 0. {
+                return None;
+            }
+
+            // Own production is consumed first, only the rest has to flow in over connections
+            let energy_received = net_inflows.get(entity).copied().unwrap_or(0.)
+                + effective_energy_output(construction_registry, terrain, construction);
+            Some((*entity, f64::clamp(energy_received / energy_demand, 0., 1.)))
+        })
+        .collect()
+}
+
 fn normalize_energy_flows(
     connection_flows: HashMap<(Entity, Entity), f64>,
 ) -> HashMap<(Entity, Entity), f64> {
@@ -105,29 +200,36 @@ mod tests {
     use super::*;
     use crate::default;
     use bevy::prelude::Entity;
-    use model::construction::ConstructionKind;
+    use model::construction_registry::ConstructionDefinition;
+    use model::game_configuration::ConnectionRangeRule;
 
     const GAME_CONFIGURATION: GameConfiguration = GameConfiguration {
-        energy_output_base: 0.,
-        energy_output_collector: 1.,
-        energy_output_extractor: 0.,
-
-        energy_input_base: 0.,
-        energy_input_collector: 0.,
-        energy_input_extractor: 1.,
+        starting_material: 0.,
+        connection_capacity: f64::INFINITY,
+        connection_range_rule: ConnectionRangeRule::Minimum,
+        connection_material_cost: 0.,
+        demolition_refund: 0.,
+        ticks_per_second: 30.,
+        zoom_scale_min: 1.,
+        zoom_scale_max: 1.,
     };
 
     /// World: C
     #[test]
     fn test_assign_energy_flows_c() {
         let construction1 = Construction {
-            kind: ConstructionKind::Collector,
+            kind: "Collector".into(),
             ..default()
         };
         let constructions = vec![(entity(1), &construction1)];
         let connections = vec![];
-        let connection_flows =
-            assign_energy_flows(&GAME_CONFIGURATION, &constructions, &connections);
+        let connection_flows = assign_energy_flows(
+            &GAME_CONFIGURATION,
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
         assert_eq!(0, connection_flows.len());
     }
 
@@ -135,11 +237,11 @@ mod tests {
     #[test]
     fn test_assign_energy_flows_c_e() {
         let collector1 = Construction {
-            kind: ConstructionKind::Collector,
+            kind: "Collector".into(),
             ..default()
         };
         let extractor = Construction {
-            kind: ConstructionKind::Extractor,
+            kind: "Extractor".into(),
             ..default()
         };
         let constructions = vec![(entity(1), &collector1), (entity(2), &extractor)];
@@ -147,14 +249,19 @@ mod tests {
         let connection1 = Connection::new_between(entity(1), entity(2));
         let connections = vec![&connection1];
 
-        let connection_flows =
-            assign_energy_flows(&GAME_CONFIGURATION, &constructions, &connections);
+        let connection_flows = assign_energy_flows(
+            &GAME_CONFIGURATION,
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
         assert_eq!(1, connection_flows.len());
         assert_eq!(Some(&1.), connection_flows.get(&(entity(1), entity(2))));
 
         // Now simluate world: C -- C -- E
         let collector2 = Construction {
-            kind: ConstructionKind::Collector,
+            kind: "Collector".into(),
             ..default()
         };
         let constructions = vec![
@@ -165,8 +272,13 @@ mod tests {
         let connection2 = Connection::new_between(entity(2), entity(3));
         let connections = vec![&connection1, &connection2];
 
-        let connection_flows =
-            assign_energy_flows(&GAME_CONFIGURATION, &constructions, &connections);
+        let connection_flows = assign_energy_flows(
+            &GAME_CONFIGURATION,
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
         assert_eq!(2, connection_flows.len());
         assert_eq!(
             Some(&0.5),
@@ -181,15 +293,15 @@ mod tests {
     #[test]
     fn test_assign_energy_flows_c_e_e() {
         let extractor1 = Construction {
-            kind: ConstructionKind::Extractor,
+            kind: "Extractor".into(),
             ..default()
         };
         let extractor2 = Construction {
-            kind: ConstructionKind::Extractor,
+            kind: "Extractor".into(),
             ..default()
         };
         let collector1 = Construction {
-            kind: ConstructionKind::Collector,
+            kind: "Collector".into(),
             ..default()
         };
         let constructions = vec![
@@ -202,8 +314,13 @@ mod tests {
         let connection2 = Connection::new_between(entity(2), entity(3));
         let connections = vec![&connection1, &connection2];
 
-        let connection_flows =
-            assign_energy_flows(&GAME_CONFIGURATION, &constructions, &connections);
+        let connection_flows = assign_energy_flows(
+            &GAME_CONFIGURATION,
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
         assert_eq!(2, connection_flows.len());
         assert_eq!(Some(&1.), connection_flows.get(&(entity(1), entity(2))));
         assert_eq!(Some(&0.5), connection_flows.get(&(entity(2), entity(3))));
@@ -213,19 +330,19 @@ mod tests {
     #[test]
     fn test_assign_energy_flows_e_c_c_e() {
         let extractor1 = Construction {
-            kind: ConstructionKind::Extractor,
+            kind: "Extractor".into(),
             ..default()
         };
         let extractor2 = Construction {
-            kind: ConstructionKind::Extractor,
+            kind: "Extractor".into(),
             ..default()
         };
         let collector1 = Construction {
-            kind: ConstructionKind::Collector,
+            kind: "Collector".into(),
             ..default()
         };
         let collector2 = Construction {
-            kind: ConstructionKind::Collector,
+            kind: "Collector".into(),
             ..default()
         };
         let constructions = vec![
@@ -240,13 +357,258 @@ mod tests {
         let connection3 = Connection::new_between(entity(3), entity(4));
         let connections = vec![&connection1, &connection2, &connection3];
 
-        let connection_flows =
-            assign_energy_flows(&GAME_CONFIGURATION, &constructions, &connections);
+        let connection_flows = assign_energy_flows(
+            &GAME_CONFIGURATION,
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
         assert_eq!(2, connection_flows.len(), "{:?}", connection_flows);
         assert_eq!(Some(&-1.), connection_flows.get(&(entity(1), entity(2))));
         assert_eq!(Some(&1.), connection_flows.get(&(entity(3), entity(4))));
     }
 
+    /// World: C -- C -- E, where the connection to E cannot carry all the energy
+    #[test]
+    fn test_assign_energy_flows_c_c_e_bottleneck() {
+        let game_configuration = GameConfiguration {
+            connection_capacity: 0.75,
+            ..GAME_CONFIGURATION
+        };
+        let collector1 = Construction {
+            kind: "Collector".into(),
+            ..default()
+        };
+        let collector2 = Construction {
+            kind: "Collector".into(),
+            ..default()
+        };
+        let extractor = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let constructions = vec![
+            (entity(1), &collector1),
+            (entity(2), &collector2),
+            (entity(3), &extractor),
+        ];
+
+        let connection1 = Connection::new_between(entity(1), entity(2));
+        let connection2 = Connection::new_between(entity(2), entity(3));
+        let connections = vec![&connection1, &connection2];
+
+        let connection_flows = assign_energy_flows(
+            &game_configuration,
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
+        assert_eq!(2, connection_flows.len(), "{:?}", connection_flows);
+        assert_eq!(Some(&0.25), connection_flows.get(&(entity(1), entity(2))));
+        assert_eq!(Some(&0.75), connection_flows.get(&(entity(2), entity(3))));
+    }
+
+    /// World:
+    /// C ------ E
+    ///  \      /
+    ///   -- B --
+    #[test]
+    fn test_assign_energy_flows_parallel_routes() {
+        let game_configuration = GameConfiguration {
+            connection_capacity: 0.6,
+            ..GAME_CONFIGURATION
+        };
+        let collector = Construction {
+            kind: "Collector".into(),
+            ..default()
+        };
+        let extractor = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let base = Construction {
+            kind: "Base".into(),
+            ..default()
+        };
+        let constructions = vec![
+            (entity(1), &collector),
+            (entity(2), &extractor),
+            (entity(3), &base),
+        ];
+
+        let connection1 = Connection::new_between(entity(1), entity(2));
+        let connection2 = Connection::new_between(entity(1), entity(3));
+        let connection3 = Connection::new_between(entity(3), entity(2));
+        let connections = vec![&connection1, &connection2, &connection3];
+
+        let connection_flows = assign_energy_flows(
+            &game_configuration,
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
+        assert_eq!(3, connection_flows.len(), "{:?}", connection_flows);
+        assert_eq!(Some(&0.6), connection_flows.get(&(entity(1), entity(2))));
+        assert_eq!(Some(&0.4), connection_flows.get(&(entity(1), entity(3))));
+        assert_eq!(Some(&-0.4), connection_flows.get(&(entity(2), entity(3))));
+    }
+
+    /// World: C   C -- E
+    #[test]
+    fn test_assign_energy_flows_spare_capacity() {
+        let collector1 = Construction {
+            kind: "Collector".into(),
+            ..default()
+        };
+        let collector2 = Construction {
+            kind: "Collector".into(),
+            ..default()
+        };
+        let extractor = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let constructions = vec![
+            (entity(1), &collector1),
+            (entity(2), &collector2),
+            (entity(3), &extractor),
+        ];
+
+        let connection1 = Connection::new_between(entity(2), entity(3));
+        let connections = vec![&connection1];
+
+        let connection_flows = assign_energy_flows(
+            &GAME_CONFIGURATION,
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
+        assert_eq!(1, connection_flows.len());
+        assert_eq!(Some(&1.), connection_flows.get(&(entity(2), entity(3))));
+    }
+
+    /// World: C -- E   C   E
+    #[test]
+    fn test_assign_energy_flows_separate_networks() {
+        let collector1 = Construction {
+            kind: "Collector".into(),
+            ..default()
+        };
+        let collector2 = Construction {
+            kind: "Collector".into(),
+            ..default()
+        };
+        let extractor1 = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let extractor2 = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let constructions = vec![
+            (entity(1), &collector1),
+            (entity(2), &extractor1),
+            (entity(3), &collector2),
+            (entity(4), &extractor2),
+        ];
+
+        let connection1 = Connection::new_between(entity(1), entity(2));
+        let connections = vec![&connection1];
+
+        // The isolated collector must not reduce the share of the connected one
+        let connection_flows = assign_energy_flows(
+            &GAME_CONFIGURATION,
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
+        assert_eq!(1, connection_flows.len());
+        assert_eq!(Some(&1.), connection_flows.get(&(entity(1), entity(2))));
+    }
+
+    /// World: C -- E -- E   E
+    #[test]
+    fn test_energy_satisfactions_c_e_e_e() {
+        let collector = Construction {
+            kind: "Collector".into(),
+            ..default()
+        };
+        let extractor1 = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let extractor2 = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let extractor3 = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let constructions = vec![
+            (entity(1), &collector),
+            (entity(2), &extractor1),
+            (entity(3), &extractor2),
+            (entity(4), &extractor3),
+        ];
+
+        let connection1 = Connection::new_between(entity(1), entity(2));
+        let connection2 = Connection::new_between(entity(2), entity(3));
+        let connections = vec![&connection1, &connection2];
+
+        let connection_flows = assign_energy_flows(
+            &GAME_CONFIGURATION,
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
+        let satisfactions = energy_satisfactions(
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connection_flows,
+        );
+        assert_eq!(3, satisfactions.len(), "{:?}", satisfactions);
+        assert_eq!(Some(&0.5), satisfactions.get(&entity(2)));
+        assert_eq!(Some(&0.5), satisfactions.get(&entity(3)));
+        assert_eq!(Some(&0.), satisfactions.get(&entity(4)));
+    }
+
+    fn construction_registry() -> ConstructionRegistry {
+        ConstructionRegistry::new(vec![
+            definition("Base", 0., 0.),
+            definition("Collector", 1., 0.),
+            definition("Extractor", 0., 1.),
+        ])
+    }
+
+    fn definition(kind: &str, energy_output: f64, energy_input: f64) -> ConstructionDefinition {
+        ConstructionDefinition {
+            kind: kind.into(),
+            name: kind.to_string(),
+            buildable: true,
+            demolishable: true,
+            material_build_demand: 0.,
+            build_duration: 1.,
+            energy_build_input: 1.,
+            energy_output,
+            energy_input,
+            material_output: 0.,
+            requires_deposit: false,
+            network_root: false,
+            influence_radius: 0.,
+            connection_range: 0.,
+            color: [1., 1., 1., 1.],
+        }
+    }
+
     fn entity(idx: u64) -> Entity {
         Entity::from_bits(idx)
     }
@@ -1,13 +1,22 @@
 use bevy::prelude::*;
 use geo::line_intersection::line_intersection;
-use geo::{coord, Line};
+use geo::{coord, EuclideanDistance, Line, Point};
 use model::connection::Connection;
-use model::construction::{Construction, ConstructionKind, ConstructionStatus};
-use model::game_configuration::GameConfiguration;
+use model::construction::{
+    Construction, ConstructionKind, ConstructionStatus, EnergySatisfaction, UnderConstructionMarker,
+};
+use model::construction_registry::ConstructionRegistry;
+use model::game::{BuildRejectionReason, PlacementRejection};
+use model::game_configuration::{ConnectionRangeRule, GameConfiguration};
+use model::map::{Deposit, Obstacle};
+use model::resources::PlayerResources;
+use model::simulation::SimulationClock;
 use model::RemovalEvent;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
 pub fn collides_with(
-    game_configuration: &GameConfiguration,
+    construction_registry: &ConstructionRegistry,
     construction: &Construction,
     other_construction: &Construction,
 ) -> bool {
@@ -15,7 +24,205 @@ pub fn collides_with(
         .location
         .distance(construction.location.clone())
         + 0.1 // account for floating point math errors
-        < (game_configuration.influence_radius(&other_construction.kind) + game_configuration.influence_radius(&construction.kind))
+        < (construction_registry.influence_radius(&other_construction.kind) + construction_registry.influence_radius(&construction.kind))
+}
+
+/// Whether the influence area of `construction` overlaps the line of a connection
+pub fn overlaps_connection(
+    construction_registry: &ConstructionRegistry,
+    construction: &Construction,
+    connection_line: &Line<f32>,
+) -> bool {
+    let point = Point::new(construction.location.x, construction.location.y);
+    point.euclidean_distance(connection_line)
+        < construction_registry.influence_radius(&construction.kind)
+}
+
+/// Checks all rules that must hold before a construction of `kind` can be built at `location`.
+/// Constructions that are still hovering (e.g. the build mode preview) are not considered.
+#[allow(clippy::too_many_arguments)]
+pub fn validate_build(
+    game_configuration: &GameConfiguration,
+    construction_registry: &ConstructionRegistry,
+    player_resources: &PlayerResources,
+    constructions: &Vec<(Entity, &Construction)>,
+    connections: &Vec<&Connection>,
+    obstacles: &Vec<&Obstacle>,
+    deposits: &Vec<&Deposit>,
+    location: &Vec2,
+    kind: &ConstructionKind,
+) -> Result<(), BuildRejectionReason> {
+    if !construction_registry.is_buildable(kind) {
+        return Err(BuildRejectionReason::NotBuildable);
+    }
+
+    let new_construction = Construction {
+        location: *location,
+        kind: kind.clone(),
+        status: ConstructionStatus::Hovering,
+    };
+    let overlaps = constructions
+        .iter()
+        .filter(|(_, construction)| construction.status != ConstructionStatus::Hovering)
+        .any(|(_, construction)| {
+            collides_with(construction_registry, &new_construction, construction)
+        });
+    if overlaps {
+        return Err(BuildRejectionReason::InfluenceOverlap);
+    }
+
+    let influence_radius = construction_registry.influence_radius(kind);
+    let blocked = obstacles
+        .iter()
+        .any(|obstacle| obstacle.location.distance(*location) < obstacle.radius + influence_radius);
+    if blocked {
+        return Err(BuildRejectionReason::ObstacleOverlap);
+    }
+
+    let overlaps_connection =
+        connection_lines(constructions, connections)
+            .iter()
+            .any(|connection_line| {
+                overlaps_connection(construction_registry, &new_construction, connection_line)
+            });
+    if overlaps_connection {
+        return Err(BuildRejectionReason::ConnectionOverlap);
+    }
+
+    // The new construction gets connected the same way as in `build_construction`, at least one
+    // of these connections has to lead to the network of the base
+    let planned_connections = planned_connections(
+        game_configuration,
+        construction_registry,
+        constructions,
+        connections,
+        location,
+        kind,
+    );
+    let base_connection = planned_connections.first().filter(|candidate| {
+        base_network(construction_registry, constructions, connections).contains(&candidate.entity)
+    });
+    let base_connection = match base_connection {
+        Some(base_connection) => base_connection,
+        None => return Err(BuildRejectionReason::OutOfNetworkReach),
+    };
+
+    if construction_registry.requires_deposit(kind) {
+        let deposit_in_reach = deposits.iter().any(|deposit| {
+            deposit.remaining > 0. && deposit.location.distance(*location) <= influence_radius
+        });
+        if !deposit_in_reach {
+            return Err(BuildRejectionReason::NoDeposit);
+        }
+    }
+
+    // Further connections are only built as long as there is material left for them
+    let material_required =
+        construction_registry.material_build_demand(kind) + base_connection.material_cost;
+    if material_required > player_resources.material_available {
+        return Err(BuildRejectionReason::InsufficientMaterial {
+            required: material_required,
+            available: player_resources.material_available,
+        });
+    }
+
+    Ok(())
+}
+
+/// Constructions that are linked to a base, directly or through other constructions. Any kind
+/// defined as `network_root` counts as a base. Hovering constructions are never part of it.
+pub fn base_network(
+    construction_registry: &ConstructionRegistry,
+    constructions: &Vec<(Entity, &Construction)>,
+    connections: &Vec<&Connection>,
+) -> HashSet<Entity> {
+    let placed_constructions: HashSet<Entity> = constructions
+        .iter()
+        .filter(|(_, construction)| construction.status != ConstructionStatus::Hovering)
+        .map(|(entity, _)| *entity)
+        .collect();
+
+    let mut network = HashSet::new();
+    let mut pending: Vec<Entity> = constructions
+        .iter()
+        .filter(|(_, construction)| {
+            construction_registry.is_network_root(&construction.kind)
+                && construction.status != ConstructionStatus::Hovering
+        })
+        .map(|(entity, _)| *entity)
+        .collect();
+    while let Some(entity) = pending.pop() {
+        if !network.insert(entity) {
+            continue;
+        }
+
+        for connection in connections.iter().filter(|c| c.connects_to(entity)) {
+            let (entity1, entity2) = *connection.between();
+            let neighbour = if entity1 == entity { entity2 } else { entity1 };
+            if placed_constructions.contains(&neighbour) && !network.contains(&neighbour) {
+                pending.push(neighbour);
+            }
+        }
+    }
+
+    network
+}
+
+/// Keeps the `PlacementRejection` of the hovering build mode construction up to date, so the
+/// player sees whether and why it cannot be built at its current location.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn placement_validation_system(
+    mut commands: Commands,
+    game_configuration: Res<GameConfiguration>,
+    construction_registry: Res<ConstructionRegistry>,
+    player_resources: Res<PlayerResources>,
+    hovering_query: Query<
+        (Entity, &Construction, Option<&PlacementRejection>),
+        With<UnderConstructionMarker>,
+    >,
+    construction_query: Query<(Entity, &Construction)>,
+    connection_query: Query<&Connection>,
+    obstacle_query: Query<&Obstacle>,
+    deposit_query: Query<&Deposit>,
+) {
+    for (entity, construction, placement_rejection) in hovering_query.iter() {
+        let constructions = construction_query.iter().collect();
+        let connections = connection_query.iter().collect();
+        let obstacles = obstacle_query.iter().collect();
+        let deposits = deposit_query.iter().collect();
+        let rejection = PlacementRejection(
+            validate_build(
+                &game_configuration,
+                &construction_registry,
+                &player_resources,
+                &constructions,
+                &connections,
+                &obstacles,
+                &deposits,
+                &construction.location,
+                &construction.kind,
+            )
+            .err(),
+        );
+
+        if placement_rejection != Some(&rejection) {
+            commands.entity(entity).insert(rejection);
+        }
+    }
+}
+
+/// Finds the closest deposit that is not yet exhausted within `radius` around `location`
+pub fn deposit_at<'a>(
+    deposits: impl Iterator<Item = (Entity, &'a Deposit)>,
+    location: Vec2,
+    radius: f32,
+) -> Option<Entity> {
+    deposits
+        .filter(|(_, deposit)| deposit.remaining > 0.)
+        .map(|(entity, deposit)| (entity, deposit.location.distance(location)))
+        .filter(|(_, distance)| *distance <= radius)
+        .min_by(|(_, distance1), (_, distance2)| distance1.total_cmp(distance2))
+        .map(|(entity, _)| entity)
 }
 
 pub(crate) fn on_construction_remove_system(
@@ -28,61 +235,598 @@ pub(crate) fn on_construction_remove_system(
     }
 }
 
+/// Advances the progress of all construction sites according to the energy they receive. Once
+/// completed, the construction starts operating. Kinds without a build duration are completed
+/// right away.
+pub(crate) fn construction_progress_system(
+    clock: Res<SimulationClock>,
+    construction_registry: Res<ConstructionRegistry>,
+    mut query: Query<(&mut Construction, &EnergySatisfaction)>,
+) {
+    for (mut construction, energy_satisfaction) in query.iter_mut() {
+        if let ConstructionStatus::Building { progress } = construction.status {
+            if energy_satisfaction.0 <= 0. {
+                continue;
+            }
+
+            let build_duration = construction_registry.build_duration(&construction.kind);
+            let progress = if build_duration > 0. {
+                progress
+                    + clock.tick_duration as f32 * energy_satisfaction.0 as f32 / build_duration
+            } else {
+                1.
+            };
+            if progress >= 1. {
+                debug!("Construction of {:?} completed", construction.kind);
+                construction.as_mut().status = ConstructionStatus::Operating;
+            } else {
+                construction.as_mut().status = ConstructionStatus::Building { progress };
+            }
+        }
+    }
+}
+
+/// Removes a construction through the usual removal path and refunds a share of the material
+/// that was needed to build it.
+pub(crate) fn demolish_construction(
+    game_configuration: &Res<GameConfiguration>,
+    construction_registry: &Res<ConstructionRegistry>,
+    player_resources: &mut ResMut<PlayerResources>,
+    removal_event_writer: &mut EventWriter<RemovalEvent<Construction>>,
+    entity: Entity,
+    construction: &Construction,
+) {
+    let refund = game_configuration.demolition_refund
+        * construction_registry.material_build_demand(&construction.kind);
+    player_resources.as_mut().material_available += refund;
+
+    removal_event_writer.send(RemovalEvent {
+        entity,
+        component: construction.clone(),
+    });
+}
+
+/// Lines between the constructions of all existing connections
+pub fn connection_lines(
+    constructions: &Vec<(Entity, &Construction)>,
+    connections: &Vec<&Connection>,
+) -> Vec<Line<f32>> {
+    let location_map: HashMap<Entity, Vec2> = constructions
+        .iter()
+        .map(|(entity, construction)| (*entity, construction.location))
+        .collect();
+    connections
+        .iter()
+        .filter_map(|connection| {
+            let location1 = location_map.get(&connection.between().0)?;
+            let location2 = location_map.get(&connection.between().1)?;
+            Some(Line::new(
+                coord! { x: location1.x, y: location1.y },
+                coord! { x: location2.x, y: location2.y },
+            ))
+        })
+        .collect()
+}
+
+/// Total order of locations, by x first and then by y
+pub fn compare_locations(location1: &Vec2, location2: &Vec2) -> Ordering {
+    location1
+        .x
+        .total_cmp(&location2.x)
+        .then_with(|| location1.y.total_cmp(&location2.y))
+}
+
+/// Orders constructions by their location. Entity ids and the order of queries differ between a
+/// game with presentation and a headless replay of it, so the simulation adds up floating point
+/// numbers in this order instead.
+pub fn sort_by_location(constructions: &mut [(Entity, &Construction)]) {
+    constructions.sort_by(|(_, construction1), (_, construction2)| {
+        compare_locations(&construction1.location, &construction2.location)
+    });
+}
+
+/// Maximum length of a connection between constructions of both kinds
+pub fn max_connection_distance(
+    game_configuration: &GameConfiguration,
+    construction_registry: &ConstructionRegistry,
+    kind1: &ConstructionKind,
+    kind2: &ConstructionKind,
+) -> f32 {
+    let range1 = construction_registry.connection_range(kind1);
+    let range2 = construction_registry.connection_range(kind2);
+    match game_configuration.connection_range_rule {
+        ConnectionRangeRule::Minimum => f32::min(range1, range2),
+        ConnectionRangeRule::Sum => range1 + range2,
+    }
+}
+
+/// A connection must not be longer than the maximum connection distance of both constructions
+/// and must not cross any existing connection.
+pub fn is_connection_allowed(
+    game_configuration: &GameConfiguration,
+    construction_registry: &ConstructionRegistry,
+    construction1: &Construction,
+    construction2: &Construction,
+    existing_connection_lines: &[Line<f32>],
+) -> bool {
+    let max_distance = max_connection_distance(
+        game_configuration,
+        construction_registry,
+        &construction1.kind,
+        &construction2.kind,
+    );
+    is_within_connection_distance(
+        max_distance,
+        &construction1.location,
+        &construction2.location,
+    ) && !crosses_any_connection(
+        &construction1.location,
+        &construction2.location,
+        existing_connection_lines,
+    )
+}
+
+fn is_within_connection_distance(max_distance: f32, location1: &Vec2, location2: &Vec2) -> bool {
+    let distance = location1.distance(*location2);
+    distance > 0. && distance < max_distance
+}
+
+/// Whether a line between both locations would cross any existing connection. Lines that merely
+/// touch at a construction do not count as crossing.
+fn crosses_any_connection(
+    location1: &Vec2,
+    location2: &Vec2,
+    existing_connection_lines: &[Line<f32>],
+) -> bool {
+    let connection_line_candidate = Line::new(
+        coord! { x: location1.x, y: location1.y },
+        coord! { x: location2.x, y: location2.y },
+    );
+    existing_connection_lines
+        .iter()
+        .any(|existing_connection_line| {
+            line_intersection(*existing_connection_line, connection_line_candidate)
+                .map(|intersect| intersect.is_proper())
+                .unwrap_or(false)
+        })
+}
+
+/// Material a connection between both locations costs
+pub fn connection_material_cost(
+    game_configuration: &GameConfiguration,
+    location1: &Vec2,
+    location2: &Vec2,
+) -> f64 {
+    game_configuration.connection_material_cost * location1.distance(*location2) as f64
+}
+
+/// A connection that would be considered for a new construction
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionCandidate {
+    pub entity: Entity,
+    pub location: Vec2,
+    pub material_cost: f64,
+    /// Candidates that cross an existing connection are dropped when building
+    pub crosses_connection: bool,
+}
+
+/// All placed constructions within connection distance of a new construction of `kind` at
+/// `location`. These are the connections `build_construction` creates, except for those that
+/// cross an existing connection.
+pub fn connection_candidates(
+    game_configuration: &GameConfiguration,
+    construction_registry: &ConstructionRegistry,
+    constructions: &Vec<(Entity, &Construction)>,
+    existing_connection_lines: &[Line<f32>],
+    location: &Vec2,
+    kind: &ConstructionKind,
+) -> Vec<ConnectionCandidate> {
+    constructions
+        .iter()
+        .filter(|(_, construction)| construction.status != ConstructionStatus::Hovering)
+        .filter(|(_, construction)| {
+            let max_distance = max_connection_distance(
+                game_configuration,
+                construction_registry,
+                &construction.kind,
+                kind,
+            );
+            is_within_connection_distance(max_distance, &construction.location, location)
+        })
+        .map(|(entity, construction)| ConnectionCandidate {
+            entity: *entity,
+            location: construction.location,
+            material_cost: connection_material_cost(
+                game_configuration,
+                &construction.location,
+                location,
+            ),
+            crosses_connection: crosses_any_connection(
+                &construction.location,
+                location,
+                existing_connection_lines,
+            ),
+        })
+        .collect()
+}
+
+/// Connections a new construction of `kind` at `location` gets, in the order they are paid for:
+/// The cheapest connection into the network of the base comes first, all others follow from
+/// cheap to expensive. Candidates that cross an existing connection are left out.
+pub fn planned_connections(
+    game_configuration: &GameConfiguration,
+    construction_registry: &ConstructionRegistry,
+    constructions: &Vec<(Entity, &Construction)>,
+    connections: &Vec<&Connection>,
+    location: &Vec2,
+    kind: &ConstructionKind,
+) -> Vec<ConnectionCandidate> {
+    let mut planned_connections: Vec<_> = connection_candidates(
+        game_configuration,
+        construction_registry,
+        constructions,
+        &connection_lines(constructions, connections),
+        location,
+        kind,
+    )
+    .into_iter()
+    .filter(|candidate| !candidate.crosses_connection)
+    .collect();
+    planned_connections.sort_by(|candidate1, candidate2| {
+        candidate1
+            .material_cost
+            .total_cmp(&candidate2.material_cost)
+            .then_with(|| compare_locations(&candidate1.location, &candidate2.location))
+    });
+
+    let base_network = base_network(construction_registry, constructions, connections);
+    let base_connection = planned_connections
+        .iter()
+        .position(|candidate| base_network.contains(&candidate.entity));
+    if let Some(base_connection) = base_connection {
+        let base_connection = planned_connections.remove(base_connection);
+        planned_connections.insert(0, base_connection);
+    }
+
+    planned_connections
+}
+
+/// Splits the planned connections into those that are paid for out of `material_available`, in
+/// the order of `planned_connections`, and those the player cannot afford anymore
+pub fn affordable_connections(
+    planned_connections: Vec<ConnectionCandidate>,
+    mut material_available: f64,
+) -> (Vec<ConnectionCandidate>, Vec<ConnectionCandidate>) {
+    let mut affordable = Vec::new();
+    let mut unaffordable = Vec::new();
+    for candidate in planned_connections {
+        if candidate.material_cost > material_available {
+            unaffordable.push(candidate);
+        } else {
+            material_available -= candidate.material_cost;
+            affordable.push(candidate);
+        }
+    }
+    (affordable, unaffordable)
+}
+
+/// Checks whether two existing constructions may be connected manually: Both must exist, must not
+/// be connected yet and the connection must be allowed by `is_connection_allowed`.
+pub fn validate_connection(
+    game_configuration: &GameConfiguration,
+    construction_registry: &ConstructionRegistry,
+    construction_query: &Query<(Entity, &Construction)>,
+    connection_query: &Query<&Connection>,
+    entity1: Entity,
+    entity2: Entity,
+) -> bool {
+    if entity1 == entity2 {
+        return false;
+    }
+
+    let (construction1, construction2) = match (
+        construction_query.get(entity1),
+        construction_query.get(entity2),
+    ) {
+        (Ok((_, construction1)), Ok((_, construction2))) => (construction1, construction2),
+        _ => return false,
+    };
+
+    let already_connected = connection_query
+        .iter()
+        .any(|connection| connection.connects_to(entity1) && connection.connects_to(entity2));
+    if already_connected {
+        return false;
+    }
+
+    is_connection_allowed(
+        game_configuration,
+        construction_registry,
+        construction1,
+        construction2,
+        &connection_lines(
+            &construction_query.iter().collect(),
+            &connection_query.iter().collect(),
+        ),
+    )
+}
+
+/// Finds the construction closest to `location`, as long as it is not further away than
+/// `radius`. Hovering constructions are ignored.
+pub fn construction_at<'a>(
+    constructions: impl Iterator<Item = (Entity, &'a Construction)>,
+    location: Vec2,
+    radius: f32,
+) -> Option<Entity> {
+    constructions
+        .filter(|(_, construction)| construction.status != ConstructionStatus::Hovering)
+        .map(|(entity, construction)| (entity, construction.location.distance(location)))
+        .filter(|(_, distance)| *distance <= radius)
+        .min_by(|(_, distance1), (_, distance2)| distance1.total_cmp(distance2))
+        .map(|(entity, _)| entity)
+}
+
+/// Finds the connection whose line passes closest to `location`, as long as it is not further
+/// away than `max_distance`.
+pub fn connection_at<'a>(
+    connections: impl Iterator<Item = (Entity, &'a Connection)>,
+    construction_query: &Query<(Entity, &Construction)>,
+    location: Vec2,
+    max_distance: f32,
+) -> Option<Entity> {
+    let point = Point::new(location.x, location.y);
+    connections
+        .filter_map(|(entity, connection)| {
+            let (_, construction1) = construction_query.get(connection.between().0).ok()?;
+            let (_, construction2) = construction_query.get(connection.between().1).ok()?;
+            let line = Line::new(
+                coord! { x: construction1.location.x, y: construction1.location.y },
+                coord! { x: construction2.location.x, y: construction2.location.y },
+            );
+            Some((entity, point.euclidean_distance(&line)))
+        })
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by(|(_, distance1), (_, distance2)| distance1.total_cmp(distance2))
+        .map(|(entity, _)| entity)
+}
+
+/// Builds a construction of `kind` at `location` and connects it to the constructions in range.
+/// The material for the construction is always charged, connections are only built while the
+/// player can afford them.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn build_construction(
     commands: &mut Commands,
-    game_configuration: &Res<GameConfiguration>,
+    game_configuration: &GameConfiguration,
+    construction_registry: &ConstructionRegistry,
+    player_resources: &mut PlayerResources,
     construction_query: &Query<(Entity, &Construction)>,
     connection_query: &Query<&Connection>,
     location: &Vec2,
     kind: &ConstructionKind,
 ) {
+    player_resources.material_available -= construction_registry.material_build_demand(kind);
+
     let new_construction_entity = commands
         .spawn()
         .insert(Construction {
-            location: location.clone(),
+            location: *location,
             kind: kind.clone(),
-            status: ConstructionStatus::Operating,
+            status: ConstructionStatus::Building { progress: 0. },
         })
         .id();
 
-    let existing_connection_lines: Vec<_> = connection_query
-        .iter()
-        .map(|connection| {
-            let (_, construction1) = construction_query.get(connection.between().0).unwrap();
-            let (_, construction2) = construction_query.get(connection.between().1).unwrap();
-            Line::new(
-                coord! { x: construction1.location.x, y: construction1.location.y },
-                coord! { x: construction2.location.x, y: construction2.location.y },
-            )
-        })
-        .collect();
+    let planned_connections = planned_connections(
+        game_configuration,
+        construction_registry,
+        &construction_query.iter().collect(),
+        &connection_query.iter().collect(),
+        location,
+        kind,
+    );
+    let (affordable_connections, unaffordable_connections) =
+        affordable_connections(planned_connections, player_resources.material_available);
+    for candidate in unaffordable_connections.iter() {
+        debug!("Cannot afford connection to {:?}", candidate.entity);
+    }
+    for candidate in affordable_connections.iter() {
+        player_resources.material_available -= candidate.material_cost;
+        let connection = Connection::new_between(candidate.entity, new_construction_entity);
+        commands.spawn().insert(connection);
+    }
+}
 
-    construction_query
-        .iter()
-        .filter(|(_, construction)| {
-            let distance = construction.location.distance(*location);
-            distance > 0. && distance < game_configuration.max_connection_distance()
-        })
-        .map(|(entity_in_range, construction)| {
-            let connection = Connection::new_between(entity_in_range, new_construction_entity);
-            (connection, construction)
-        })
-        .filter(|(_, construction)| {
-            existing_connection_lines
-                .iter()
-                .all(|existing_connection_line| {
-                    let connection_line_candidate = Line::new(
-                        coord! { x: construction.location.x, y: construction.location.y },
-                        coord! { x: location.x, y: location.y },
-                    );
-                    let intersection =
-                        line_intersection(*existing_connection_line, connection_line_candidate);
-                    !intersection
-                        .map(|intersect| intersect.is_proper())
-                        .unwrap_or(false)
-                })
-        })
-        .for_each(|(connection, _)| {
-            commands.spawn().insert(connection);
-        });
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default;
+    use model::construction_registry::ConstructionDefinition;
+
+    /// World: B -- C -- E   C -- E
+    #[test]
+    fn test_base_network() {
+        let construction_registry = ConstructionRegistry::new(vec![
+            definition("Base", 150.),
+            definition("Collector", 150.),
+            definition("Extractor", 150.),
+        ]);
+        let base = Construction::default();
+        let collector = Construction {
+            kind: "Collector".into(),
+            ..default()
+        };
+        let extractor = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let constructions = vec![
+            (entity(1), &base),
+            (entity(2), &collector),
+            (entity(3), &extractor),
+            (entity(4), &collector),
+            (entity(5), &extractor),
+        ];
+        let connection12 = Connection::new_between(entity(1), entity(2));
+        let connection23 = Connection::new_between(entity(2), entity(3));
+        let connection45 = Connection::new_between(entity(4), entity(5));
+        let connections = vec![&connection12, &connection23, &connection45];
+
+        let network = base_network(&construction_registry, &constructions, &connections);
+        assert_eq!(HashSet::from([entity(1), entity(2), entity(3)]), network);
+    }
+
+    /// World: B   E -- E   x   C, where E -- E is a vertical line between B and x
+    #[test]
+    fn test_connection_candidates() {
+        let game_configuration = GameConfiguration {
+            connection_range_rule: ConnectionRangeRule::Minimum,
+            ..default()
+        };
+        let construction_registry = ConstructionRegistry::new(vec![
+            definition("Base", 150.),
+            definition("Collector", 150.),
+            definition("Extractor", 150.),
+        ]);
+        let base = Construction::default();
+        let extractor_top = Construction {
+            location: Vec2::new(100., 60.),
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let extractor_bottom = Construction {
+            location: Vec2::new(100., -60.),
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let collector = Construction {
+            location: Vec2::new(1000., 0.),
+            kind: "Collector".into(),
+            ..default()
+        };
+        let constructions = vec![
+            (entity(1), &base),
+            (entity(2), &extractor_top),
+            (entity(3), &extractor_bottom),
+            (entity(4), &collector),
+        ];
+        let existing_connection_lines = vec![Line::new(
+            coord! { x: 100., y: 60. },
+            coord! { x: 100., y: -60. },
+        )];
+
+        let candidates = connection_candidates(
+            &game_configuration,
+            &construction_registry,
+            &constructions,
+            &existing_connection_lines,
+            &Vec2::new(140., 0.),
+            &"Collector".into(),
+        );
+        let candidates: Vec<_> = candidates
+            .iter()
+            .map(|candidate| (candidate.entity, candidate.crosses_connection))
+            .collect();
+        assert_eq!(
+            vec![(entity(1), true), (entity(2), false), (entity(3), false)],
+            candidates
+        );
+    }
+
+    /// World: B     x   C, where C is closer to x but not connected to B
+    #[test]
+    fn test_planned_connections() {
+        let game_configuration = GameConfiguration {
+            connection_material_cost: 1.,
+            ..default()
+        };
+        let construction_registry = ConstructionRegistry::new(vec![
+            definition("Base", 150.),
+            definition("Collector", 150.),
+        ]);
+        let base = Construction::default();
+        let collector = Construction {
+            location: Vec2::new(100., 0.),
+            kind: "Collector".into(),
+            ..default()
+        };
+        let constructions = vec![(entity(1), &base), (entity(2), &collector)];
+
+        let planned = planned_connections(
+            &game_configuration,
+            &construction_registry,
+            &constructions,
+            &vec![],
+            &Vec2::new(60., 0.),
+            &"Collector".into(),
+        );
+        let planned: Vec<_> = planned
+            .iter()
+            .map(|candidate| (candidate.entity, candidate.material_cost))
+            .collect();
+        assert_eq!(vec![(entity(1), 60.), (entity(2), 40.)], planned);
+    }
+
+    #[test]
+    fn test_affordable_connections() {
+        let candidate = |idx: u64, material_cost: f64| ConnectionCandidate {
+            entity: entity(idx),
+            location: Vec2::ZERO,
+            material_cost,
+            crosses_connection: false,
+        };
+        let planned = vec![candidate(1, 60.), candidate(2, 50.), candidate(3, 30.)];
+
+        let (affordable, unaffordable) = affordable_connections(planned, 100.);
+        assert_eq!(vec![candidate(1, 60.), candidate(3, 30.)], affordable);
+        assert_eq!(vec![candidate(2, 50.)], unaffordable);
+    }
+
+    #[test]
+    fn test_max_connection_distance() {
+        let construction_registry = ConstructionRegistry::new(vec![
+            definition("Collector", 150.),
+            definition("Extractor", 100.),
+        ]);
+        let minimum = GameConfiguration {
+            connection_range_rule: ConnectionRangeRule::Minimum,
+            ..default()
+        };
+        let sum = GameConfiguration {
+            connection_range_rule: ConnectionRangeRule::Sum,
+            ..default()
+        };
+
+        let (collector, extractor) = ("Collector".into(), "Extractor".into());
+        assert_eq!(
+            100.,
+            max_connection_distance(&minimum, &construction_registry, &collector, &extractor)
+        );
+        assert_eq!(
+            250.,
+            max_connection_distance(&sum, &construction_registry, &collector, &extractor)
+        );
+    }
+
+    fn definition(kind: &str, connection_range: f32) -> ConstructionDefinition {
+        ConstructionDefinition {
+            kind: kind.into(),
+            name: kind.to_string(),
+            buildable: true,
+            demolishable: true,
+            material_build_demand: 0.,
+            build_duration: 1.,
+            energy_build_input: 0.,
+            energy_output: 0.,
+            energy_input: 0.,
+            material_output: 0.,
+            requires_deposit: false,
+            network_root: kind == "Base",
+            influence_radius: 0.,
+            connection_range,
+            color: [1., 1., 1., 1.],
+        }
+    }
+
+    fn entity(idx: u64) -> Entity {
+        Entity::from_bits(idx)
+    }
 }
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use model::{
+    connection::Connection,
+    construction::{Construction, ConstructionStatus, EnergySatisfaction, Idle},
+    construction_registry::ConstructionRegistry,
+    game_configuration::GameConfiguration,
+    terrain::Terrain,
+    RemovalEvent,
+};
+
+#[derive(Default)]
+pub(crate) struct UpdateConnectionEnergyFlows;
+
+/// Every construction keeps track of how much of its energy demand is satisfied
+pub(crate) fn on_construction_enter_system(
+    mut commands: Commands,
+    query: Query<(Entity, &Construction), Added<Construction>>,
+    construction_registry: Res<ConstructionRegistry>,
+) {
+    for (entity, construction) in query.iter() {
+        let energy_demand = construction_registry.construction_energy_input(construction);
+        let energy_satisfaction = if energy_demand > 0. { 0. } else { 1. };
+        commands
+            .entity(entity)
+            .insert(EnergySatisfaction(energy_satisfaction));
+    }
+}
+
+/// Changes to constructions that alter the energy networks
+type ConstructionTriggerFilter = Or<(
+    Added<Construction>,
+    Changed<Construction>,
+    Added<EnergySatisfaction>,
+    Added<Idle>,
+)>;
+
+/// Energy flows are updated whenever the construction graph or the configuration changes
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn emit_update_connection_energy_flows_system(
+    construction_trigger_query: Query<(), ConstructionTriggerFilter>,
+    connection_trigger_query: Query<(), Added<Connection>>,
+    construction_removal_events: EventReader<RemovalEvent<Construction>>,
+    connection_removal_events: EventReader<RemovalEvent<Connection>>,
+    game_configuration: Res<GameConfiguration>,
+    construction_registry: Res<ConstructionRegistry>,
+    terrain: Res<Terrain>,
+    mut event_writer: EventWriter<UpdateConnectionEnergyFlows>,
+) {
+    if construction_trigger_query.is_empty()
+        && connection_trigger_query.is_empty()
+        && construction_removal_events.is_empty()
+        && connection_removal_events.is_empty()
+        && !game_configuration.is_changed()
+        && !construction_registry.is_changed()
+        && !terrain.is_changed()
+    {
+        return;
+    }
+
+    construction_removal_events.clear();
+    connection_removal_events.clear();
+
+    event_writer.send_default();
+}
+
+pub(crate) fn on_update_connection_energy_flows_system(
+    event_reader: EventReader<UpdateConnectionEnergyFlows>,
+    construction_query: Query<(Entity, &Construction), Without<Idle>>,
+    mut connection_query: Query<&mut Connection>,
+    mut energy_satisfaction_query: Query<&mut EnergySatisfaction>,
+    game_configuration: Res<GameConfiguration>,
+    construction_registry: Res<ConstructionRegistry>,
+    terrain: Res<Terrain>,
+) {
+    if event_reader.is_empty() {
+        return;
+    }
+    event_reader.clear();
+
+    trace!("Updating energy flow values in all connections...");
+
+    // The hovering construction of the build mode is not part of any network, neither are idle
+    // constructions that stopped drawing energy. The order of the others must not depend on
+    // entity ids, see `sort_by_location`.
+    let mut constructions: Vec<_> = construction_query
+        .iter()
+        .filter(|(_, construction)| construction.status != ConstructionStatus::Hovering)
+        .collect();
+    crate::construction::sort_by_location(&mut constructions);
+    let connections = connection_query
+        .iter()
+        .filter(|connection| {
+            construction_query.contains(connection.between().0)
+                && construction_query.contains(connection.between().1)
+        })
+        .collect();
+
+    // Assign energy flows to each connection
+    let energy_flows = crate::connection_energy_flow::assign_energy_flows(
+        &game_configuration,
+        &construction_registry,
+        &terrain,
+        &constructions,
+        &connections,
+    );
+
+    // Map assigned energy flows to the connection objects
+    for mut connection in connection_query.iter_mut() {
+        connection.as_mut().energy_flow = energy_flows
+            .get(connection.between())
+            .copied()
+            .unwrap_or(0.);
+    }
+
+    // Let each consumer know how much of its demand is covered
+    let energy_satisfactions = crate::connection_energy_flow::energy_satisfactions(
+        &construction_registry,
+        &terrain,
+        &constructions,
+        &energy_flows,
+    );
+    for (entity, _) in constructions.iter() {
+        if let Ok(mut energy_satisfaction) = energy_satisfaction_query.get_mut(*entity) {
+            // Constructions without any demand are always satisfied
+            energy_satisfaction.as_mut().0 =
+                energy_satisfactions.get(entity).copied().unwrap_or(1.);
+        }
+    }
+}
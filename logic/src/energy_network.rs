@@ -0,0 +1,138 @@
+use crate::terrain::effective_energy_output;
+use bevy::prelude::*;
+use model::connection::Connection;
+use model::construction::Construction;
+use model::construction_registry::ConstructionRegistry;
+use model::energy_network::EnergyNetwork;
+use model::terrain::Terrain;
+use petgraph::unionfind::UnionFind;
+use std::collections::HashMap;
+
+/// Splits the construction graph into its connected components and sums up the energy supply and
+/// demand of each of them. Networks are returned in the order of their first construction.
+pub fn find_energy_networks(
+    construction_registry: &ConstructionRegistry,
+    terrain: &Terrain,
+    constructions: &Vec<(Entity, &Construction)>,
+    connections: &Vec<&Connection>,
+) -> Vec<EnergyNetwork> {
+    let entity_to_index_map: HashMap<Entity, usize> = constructions
+        .iter()
+        .enumerate()
+        .map(|(index, (entity, _))| (*entity, index))
+        .collect();
+
+    let mut components = UnionFind::new(constructions.len());
+    for connection in connections.iter() {
+        let index0_opt = entity_to_index_map.get(&connection.between().0);
+        let index1_opt = entity_to_index_map.get(&connection.between().1);
+        if let (Some(index0), Some(index1)) = (index0_opt, index1_opt) {
+            components.union(*index0, *index1);
+        }
+    }
+
+    let mut component_to_network_map: HashMap<usize, usize> = HashMap::new();
+    let mut networks: Vec<EnergyNetwork> = Vec::new();
+    for (index, (entity, construction)) in constructions.iter().enumerate() {
+        let network_index = *component_to_network_map
+            .entry(components.find(index))
+            .or_insert_with(|| {
+                networks.push(EnergyNetwork {
+                    constructions: Vec::new(),
+                    energy_supply: 0.,
+                    energy_demand: 0.,
+                });
+                networks.len() - 1
+            });
+
+        let network = &mut networks[network_index];
+        network.constructions.push(*entity);
+        network.energy_supply +=
+            effective_energy_output(construction_registry, terrain, construction);
+        network.energy_demand += construction_registry.construction_energy_input(construction);
+    }
+
+    networks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default;
+    use model::construction_registry::ConstructionDefinition;
+
+    /// World: C   E -- E
+    #[test]
+    fn test_find_energy_networks_c_e_e() {
+        let collector = Construction {
+            kind: "Collector".into(),
+            ..default()
+        };
+        let extractor1 = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let extractor2 = Construction {
+            kind: "Extractor".into(),
+            ..default()
+        };
+        let constructions = vec![
+            (entity(1), &collector),
+            (entity(2), &extractor1),
+            (entity(3), &extractor2),
+        ];
+
+        let connection1 = Connection::new_between(entity(2), entity(3));
+        let connections = vec![&connection1];
+
+        let networks = find_energy_networks(
+            &construction_registry(),
+            &Terrain::default(),
+            &constructions,
+            &connections,
+        );
+        assert_eq!(2, networks.len(), "{:?}", networks);
+
+        assert_eq!(vec![entity(1)], networks[0].constructions);
+        assert_eq!(1., networks[0].energy_supply);
+        assert_eq!(0., networks[0].energy_demand);
+        assert!(!networks[0].is_starved());
+
+        assert_eq!(vec![entity(2), entity(3)], networks[1].constructions);
+        assert_eq!(0., networks[1].energy_supply);
+        assert_eq!(2., networks[1].energy_demand);
+        assert!(networks[1].is_starved());
+    }
+
+    fn construction_registry() -> ConstructionRegistry {
+        ConstructionRegistry::new(vec![
+            definition("Base", 0., 0.),
+            definition("Collector", 1., 0.),
+            definition("Extractor", 0., 1.),
+        ])
+    }
+
+    fn definition(kind: &str, energy_output: f64, energy_input: f64) -> ConstructionDefinition {
+        ConstructionDefinition {
+            kind: kind.into(),
+            name: kind.to_string(),
+            buildable: true,
+            demolishable: true,
+            material_build_demand: 0.,
+            build_duration: 1.,
+            energy_build_input: 1.,
+            energy_output,
+            energy_input,
+            material_output: 0.,
+            requires_deposit: false,
+            network_root: false,
+            influence_radius: 0.,
+            connection_range: 0.,
+            color: [1., 1., 1., 1.],
+        }
+    }
+
+    fn entity(idx: u64) -> Entity {
+        Entity::from_bits(idx)
+    }
+}
@@ -1,46 +1,175 @@
+use bevy::ecs::event::{Events, ManualEventReader};
 use bevy::prelude::*;
 use model::connection::Connection;
+use model::construction_registry::ConstructionRegistry;
 use model::game_configuration::GameConfiguration;
+use model::map::{Deposit, Obstacle};
+use model::resources::PlayerResources;
+use model::RemovalEvent;
 use model::{
-    construction::Construction,
-    game::{GameEvent, GameMode},
+    construction::{Construction, ConstructionStatus},
+    game::{BuildRejectionReason, GameEvent, GameMode},
 };
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn process_game_events(
     mut commands: Commands,
-    mut game_events: EventReader<GameEvent>,
+    mut game_events: ResMut<Events<GameEvent>>,
+    mut game_event_reader: Local<ManualEventReader<GameEvent>>,
     current_game_mode: Res<GameMode>,
     game_configuration: Res<GameConfiguration>,
+    construction_registry: Res<ConstructionRegistry>,
+    mut player_resources: ResMut<PlayerResources>,
     construction_query: Query<(Entity, &Construction)>,
     connection_query: Query<&Connection>,
+    obstacle_query: Query<&Obstacle>,
+    deposit_query: Query<&Deposit>,
+    mut construction_removal_event_writer: EventWriter<RemovalEvent<Construction>>,
+    mut connection_removal_event_writer: EventWriter<RemovalEvent<Connection>>,
 ) {
-    for event in game_events.iter() {
+    // Events are collected first, as processing them may emit further game events
+    let events: Vec<GameEvent> = game_event_reader.iter(&game_events).cloned().collect();
+    // Constructions built for earlier events are only spawned once the commands are applied, so
+    // the queries do not contain them yet
+    let mut pending_constructions: Vec<Construction> = Vec::new();
+    for event in events.iter() {
         debug!("Processing game event: {:?}", event);
         match event {
             &GameEvent::SwitchToGameMode(ref target_mode) => {
-                match (current_game_mode.as_ref(), target_mode) {
-                    (&GameMode::Idle, _) => commands.insert_resource(target_mode.clone()),
-                    (&GameMode::Building(_), &GameMode::Idle) => {
-                        commands.insert_resource(target_mode.clone())
-                    }
-                    _ => todo!(
-                        "Switch from {:?} to {:?}",
-                        current_game_mode.as_ref(),
-                        target_mode
-                    ),
-                };
+                // Each mode cleans up after itself once the game mode changes, so any switch is fine
+                if current_game_mode.as_ref() != target_mode {
+                    commands.insert_resource(target_mode.clone());
+                }
             }
             &GameEvent::BuildConstruction(ref location, ref kind) => {
-                crate::construction::build_construction(
-                    &mut commands,
+                let constructions = construction_query.iter().collect();
+                let connections = connection_query.iter().collect();
+                let obstacles = obstacle_query.iter().collect();
+                let deposits = deposit_query.iter().collect();
+                let new_construction = Construction {
+                    location: *location,
+                    kind: kind.clone(),
+                    status: ConstructionStatus::Hovering,
+                };
+                let overlaps_pending = pending_constructions.iter().any(|pending_construction| {
+                    crate::construction::collides_with(
+                        &construction_registry,
+                        pending_construction,
+                        &new_construction,
+                    )
+                });
+                let validation = if overlaps_pending {
+                    Err(BuildRejectionReason::InfluenceOverlap)
+                } else {
+                    crate::construction::validate_build(
+                        &game_configuration,
+                        &construction_registry,
+                        &player_resources,
+                        &constructions,
+                        &connections,
+                        &obstacles,
+                        &deposits,
+                        location,
+                        kind,
+                    )
+                };
+
+                match validation {
+                    Ok(()) => {
+                        crate::construction::build_construction(
+                            &mut commands,
+                            &game_configuration,
+                            &construction_registry,
+                            player_resources.as_mut(),
+                            &construction_query,
+                            &connection_query,
+                            location,
+                            kind,
+                        );
+                        pending_constructions.push(new_construction);
+                    }
+                    Err(reason) => {
+                        debug!("Rejected building {:?} at {}: {}", kind, location, reason);
+                        game_events.send(GameEvent::BuildRejected {
+                            location: *location,
+                            kind: kind.clone(),
+                            reason,
+                        });
+                    }
+                }
+                commands.insert_resource(GameMode::Idle);
+            }
+            &GameEvent::DemolishConstruction(entity) => {
+                match construction_query.get(entity) {
+                    Ok((_, construction))
+                        if construction_registry.is_demolishable(&construction.kind) =>
+                    {
+                        crate::construction::demolish_construction(
+                            &game_configuration,
+                            &construction_registry,
+                            &mut player_resources,
+                            &mut construction_removal_event_writer,
+                            entity,
+                            construction,
+                        );
+                    }
+                    Ok((_, construction)) => {
+                        debug!("{:?} cannot be demolished", construction.kind);
+                    }
+                    Err(_) => warn!("Construction {:?} to demolish not found", entity),
+                }
+                commands.insert_resource(GameMode::Idle);
+            }
+            &GameEvent::ConnectConstructions(entity1, entity2) => {
+                let valid = crate::construction::validate_connection(
                     &game_configuration,
+                    &construction_registry,
                     &construction_query,
                     &connection_query,
-                    location,
-                    kind,
+                    entity1,
+                    entity2,
                 );
+                let material_cost = match (
+                    construction_query.get(entity1),
+                    construction_query.get(entity2),
+                ) {
+                    (Ok((_, construction1)), Ok((_, construction2))) => {
+                        crate::construction::connection_material_cost(
+                            &game_configuration,
+                            &construction1.location,
+                            &construction2.location,
+                        )
+                    }
+                    _ => 0.,
+                };
+
+                if !valid {
+                    debug!("Connection between {:?} not allowed", (entity1, entity2));
+                } else if material_cost > player_resources.material_available {
+                    debug!(
+                        "Connection between {:?} costs {:.0} material, which is not available",
+                        (entity1, entity2),
+                        material_cost
+                    );
+                } else {
+                    player_resources.as_mut().material_available -= material_cost;
+                    commands
+                        .spawn()
+                        .insert(Connection::new_between(entity1, entity2));
+                }
+                commands.insert_resource(GameMode::Idle);
+            }
+            &GameEvent::CutConnection(entity) => {
+                match connection_query.get(entity) {
+                    Ok(connection) => connection_removal_event_writer.send(RemovalEvent {
+                        entity,
+                        component: connection.clone(),
+                    }),
+                    Err(_) => warn!("Connection {:?} to cut not found", entity),
+                }
                 commands.insert_resource(GameMode::Idle);
-            } // _ => panic!("Game event type not implemented: {:?}", event),
+            }
+            &GameEvent::BuildRejected { .. } => (),
         }
     }
 }
@@ -1,18 +1,36 @@
 use bevy::prelude::*;
+use model::simulation::SimulationApp;
 
 mod connection;
 pub mod connection_energy_flow;
 pub mod construction;
+mod energy_flow;
+pub mod energy_network;
 pub mod game_events;
+pub mod map_generation;
+pub mod replay;
+pub mod scenario;
+pub mod snapshot;
+pub mod terrain;
 mod utils;
 
 pub struct LogicPlugin;
 
 impl Plugin for LogicPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(game_events::process_game_events)
-            .add_system(construction::on_construction_remove_system)
-            .add_system(connection::on_connection_remove_system);
+        // Replayed events are sent and recorded right before the game events are processed
+        app.add_simulation_system(replay::replay_game_events_system)
+            .add_simulation_system(replay::record_game_events_system)
+            .add_simulation_system(game_events::process_game_events)
+            .add_simulation_system(construction::on_construction_remove_system)
+            .add_simulation_system(construction::construction_progress_system)
+            .add_simulation_system(construction::placement_validation_system)
+            .add_simulation_system(connection::on_connection_remove_system)
+            .add_simulation_system(connection::on_construction_remove_system)
+            .add_simulation_event::<energy_flow::UpdateConnectionEnergyFlows>()
+            .add_simulation_system(energy_flow::on_construction_enter_system)
+            .add_simulation_system(energy_flow::emit_update_connection_energy_flows_system)
+            .add_simulation_system(energy_flow::on_update_connection_energy_flows_system);
     }
 }
 
@@ -0,0 +1,259 @@
+use bevy::prelude::*;
+use model::construction::{Construction, ConstructionStatus};
+use model::map::{Deposit, Obstacle};
+use model::terrain::{Terrain, TerrainRegion};
+use rand::Rng;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+const COLUMNS: usize = 24;
+const ROWS: usize = 18;
+const TILE_SIZE: f32 = 100.;
+
+/// Region the base starts on, it covers all tiles that are not part of a patch of another region
+const BASE_REGION: usize = 0;
+const REGION_PATCH_COUNT: usize = 14;
+const REGION_PATCH_MAX_RADIUS: i32 = 3;
+
+/// Area around the base that stays free of obstacles and other regions
+const BASE_CLEARANCE: f32 = 250.;
+/// Maximum distance of the base from the center of the map
+const BASE_MAX_OFFSET: f32 = 200.;
+
+const OBSTACLE_COUNT: usize = 12;
+const OBSTACLE_MIN_RADIUS: f32 = 20.;
+const OBSTACLE_MAX_RADIUS: f32 = 60.;
+
+const DEPOSIT_COUNT: usize = 20;
+/// Deposits that are placed close to the base, so the player can start extracting right away
+const STARTING_DEPOSIT_COUNT: usize = 2;
+const DEPOSIT_MIN_AMOUNT: f64 = 100.;
+const DEPOSIT_MAX_AMOUNT: f64 = 300.;
+
+/// Gives up placing an object once this many random locations were rejected
+const MAX_PLACEMENT_ATTEMPTS: usize = 100;
+
+/// Everything that makes up a new map, before any entity is spawned
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedMap {
+    pub terrain: Terrain,
+    pub deposits: Vec<Deposit>,
+    pub obstacles: Vec<Obstacle>,
+    pub base_location: Vec2,
+}
+
+/// Generates a map from `seed`, using `regions` for its terrain, e.g. those of the terrain file.
+/// The same seed and regions always result in the same map.
+pub fn generate_map(seed: u64, regions: &[TerrainRegion]) -> GeneratedMap {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    let map_size = Vec2::new(COLUMNS as f32, ROWS as f32) * TILE_SIZE;
+    let origin = -map_size / 2.;
+
+    let base_location = Vec2::new(
+        rng.gen_range(-BASE_MAX_OFFSET..BASE_MAX_OFFSET),
+        rng.gen_range(-BASE_MAX_OFFSET..BASE_MAX_OFFSET),
+    );
+
+    let terrain = generate_terrain(&mut rng, regions, origin, base_location);
+    let obstacles = generate_obstacles(&mut rng, origin, map_size, base_location);
+    let deposits = generate_deposits(&mut rng, origin, map_size, base_location, &obstacles);
+
+    GeneratedMap {
+        terrain,
+        deposits,
+        obstacles,
+        base_location,
+    }
+}
+
+/// Spawns the base, the deposits and the obstacles of a generated map. The terrain is a resource
+/// and has to be inserted separately.
+pub fn spawn_generated_map(commands: &mut Commands, generated_map: &GeneratedMap) {
+    commands.spawn().insert(Construction {
+        location: generated_map.base_location,
+        kind: "Base".into(),
+        status: ConstructionStatus::Operating,
+    });
+
+    for deposit in generated_map.deposits.iter() {
+        commands.spawn().insert(deposit.clone());
+    }
+
+    for obstacle in generated_map.obstacles.iter() {
+        commands.spawn().insert(obstacle.clone());
+    }
+}
+
+fn generate_terrain(
+    rng: &mut ChaCha8Rng,
+    regions: &[TerrainRegion],
+    origin: Vec2,
+    base_location: Vec2,
+) -> Terrain {
+    let mut tiles = vec![BASE_REGION; COLUMNS * ROWS];
+
+    // Patches of the other regions are scattered over the base region, if there are any
+    let patch_count = if regions.len() > 1 {
+        REGION_PATCH_COUNT
+    } else {
+        0
+    };
+    for _ in 0..patch_count {
+        let region = rng.gen_range(1..regions.len());
+        let center_column = rng.gen_range(0..COLUMNS) as i32;
+        let center_row = rng.gen_range(0..ROWS) as i32;
+        let radius = rng.gen_range(1..=REGION_PATCH_MAX_RADIUS);
+
+        for row in (center_row - radius)..=(center_row + radius) {
+            for column in (center_column - radius)..=(center_column + radius) {
+                let inside_map =
+                    (0..COLUMNS as i32).contains(&column) && (0..ROWS as i32).contains(&row);
+                let inside_patch =
+                    (row - center_row).pow(2) + (column - center_column).pow(2) <= radius.pow(2);
+                if inside_map && inside_patch {
+                    tiles[row as usize * COLUMNS + column as usize] = region;
+                }
+            }
+        }
+    }
+
+    // The base always starts on the base region
+    for row in 0..ROWS {
+        for column in 0..COLUMNS {
+            let tile_center = origin + (Vec2::new(column as f32, row as f32) + 0.5) * TILE_SIZE;
+            if tile_center.distance(base_location) < BASE_CLEARANCE {
+                tiles[row * COLUMNS + column] = BASE_REGION;
+            }
+        }
+    }
+
+    Terrain {
+        regions: regions.to_vec(),
+        origin,
+        tile_size: TILE_SIZE,
+        columns: COLUMNS,
+        rows: ROWS,
+        tiles,
+    }
+}
+
+fn generate_obstacles(
+    rng: &mut ChaCha8Rng,
+    origin: Vec2,
+    map_size: Vec2,
+    base_location: Vec2,
+) -> Vec<Obstacle> {
+    let mut obstacles: Vec<Obstacle> = Vec::new();
+
+    for _ in 0..OBSTACLE_COUNT {
+        for _ in 0..MAX_PLACEMENT_ATTEMPTS {
+            let obstacle = Obstacle {
+                location: random_location(rng, origin, map_size),
+                radius: rng.gen_range(OBSTACLE_MIN_RADIUS..OBSTACLE_MAX_RADIUS),
+            };
+
+            let blocks_base =
+                obstacle.location.distance(base_location) < BASE_CLEARANCE + obstacle.radius;
+            let overlaps = obstacles.iter().any(|other| {
+                other.location.distance(obstacle.location) < other.radius + obstacle.radius
+            });
+            if !blocks_base && !overlaps {
+                obstacles.push(obstacle);
+                break;
+            }
+        }
+    }
+
+    obstacles
+}
+
+fn generate_deposits(
+    rng: &mut ChaCha8Rng,
+    origin: Vec2,
+    map_size: Vec2,
+    base_location: Vec2,
+    obstacles: &[Obstacle],
+) -> Vec<Deposit> {
+    let mut deposits: Vec<Deposit> = Vec::new();
+
+    for index in 0..DEPOSIT_COUNT {
+        for _ in 0..MAX_PLACEMENT_ATTEMPTS {
+            let location = if index < STARTING_DEPOSIT_COUNT {
+                let angle = rng.gen_range(0. ..std::f32::consts::TAU);
+                let distance = rng.gen_range(BASE_CLEARANCE / 2. ..BASE_CLEARANCE);
+                base_location + Vec2::new(angle.cos(), angle.sin()) * distance
+            } else {
+                random_location(rng, origin, map_size)
+            };
+            let remaining = rng.gen_range(DEPOSIT_MIN_AMOUNT..DEPOSIT_MAX_AMOUNT);
+
+            let blocked = obstacles
+                .iter()
+                .any(|obstacle| obstacle.location.distance(location) < obstacle.radius);
+            if !blocked {
+                deposits.push(Deposit {
+                    location,
+                    remaining,
+                });
+                break;
+            }
+        }
+    }
+
+    deposits
+}
+
+fn random_location(rng: &mut ChaCha8Rng, origin: Vec2, map_size: Vec2) -> Vec2 {
+    origin
+        + Vec2::new(
+            rng.gen_range(0. ..map_size.x),
+            rng.gen_range(0. ..map_size.y),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use model::configuration_file;
+
+    use super::*;
+
+    fn terrain_regions() -> Vec<TerrainRegion> {
+        let terrain: Terrain =
+            configuration_file::load_ron_file(Path::new("../assets/terrain.ron")).unwrap();
+        terrain.regions
+    }
+
+    #[test]
+    fn test_generate_map_same_seed() {
+        let regions = terrain_regions();
+        assert_eq!(generate_map(42, &regions), generate_map(42, &regions));
+        assert_ne!(generate_map(42, &regions), generate_map(43, &regions));
+    }
+
+    #[test]
+    fn test_generate_map_valid_base_location() {
+        let regions = terrain_regions();
+        for seed in 0..20 {
+            let map = generate_map(seed, &regions);
+
+            let region = map.terrain.region_at(map.base_location).unwrap();
+            assert_eq!("Plains", region.name, "seed {}", seed);
+
+            let blocked = map
+                .obstacles
+                .iter()
+                .any(|obstacle| obstacle.location.distance(map.base_location) < BASE_CLEARANCE);
+            assert!(!blocked, "seed {}", seed);
+
+            let starting_deposits = map
+                .deposits
+                .iter()
+                .filter(|deposit| deposit.location.distance(map.base_location) < BASE_CLEARANCE)
+                .count();
+            assert!(starting_deposits >= STARTING_DEPOSIT_COUNT, "seed {}", seed);
+        }
+    }
+}
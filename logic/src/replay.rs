@@ -0,0 +1,321 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+
+use bevy::prelude::*;
+use model::configuration_file;
+use model::connection::Connection;
+use model::construction::{Construction, ConstructionStatus, EnergySatisfaction, ProductionCycle};
+use model::game::GameEvent;
+use model::map::Deposit;
+use model::recording::{GameRecording, RecordedEvent, RecordedGameEvent};
+use model::resources::PlayerResources;
+use model::simulation::SimulationClock;
+
+use crate::construction::compare_locations;
+use crate::snapshot::{restore_snapshot, take_snapshot};
+
+/// Writes down every game event the simulation processes, while it is inserted as a resource
+pub struct GameEventRecorder {
+    recording: GameRecording,
+    start_tick: u64,
+}
+
+/// Feeds the events of a recording back into the simulation, while it is inserted as a resource
+pub struct GameEventReplay {
+    events: VecDeque<RecordedGameEvent>,
+    start_tick: u64,
+    ticks: u64,
+    end_state_hash: u64,
+}
+
+/// Starts to record the game from its current state on. The game is restored from its own
+/// snapshot right away, so that it continues from exactly the state a replay starts from.
+pub fn start_recording(world: &mut World) {
+    let snapshot = take_snapshot(world);
+    restore_snapshot(world, &snapshot);
+
+    let start_tick = world.resource::<SimulationClock>().tick;
+    world.insert_resource(GameEventRecorder {
+        recording: GameRecording {
+            snapshot,
+            events: Vec::new(),
+            ticks: 0,
+            end_state_hash: 0,
+        },
+        start_tick,
+    });
+}
+
+/// Stops recording and completes the recording with the current state, if the game was recorded
+pub fn finish_recording(world: &mut World) -> Option<GameRecording> {
+    let recorder = world.remove_resource::<GameEventRecorder>()?;
+    let mut recording = recorder.recording;
+    recording.ticks = world.resource::<SimulationClock>().tick - recorder.start_tick;
+    recording.end_state_hash = state_hash(world);
+    Some(recording)
+}
+
+/// Stops recording and writes the recording to a RON file
+pub fn save_recording(world: &mut World, path: &Path) -> Result<(), String> {
+    let recording = finish_recording(world).ok_or("The game is not being recorded")?;
+    configuration_file::save_ron_file(path, &recording)
+}
+
+pub fn load_recording(path: &Path) -> Result<GameRecording, String> {
+    configuration_file::load_ron_file(path)
+}
+
+/// Restores the snapshot of the recording and replays its events from the next tick on
+pub fn start_replay(world: &mut World, recording: &GameRecording) {
+    restore_snapshot(world, &recording.snapshot);
+
+    let start_tick = world.resource::<SimulationClock>().tick;
+    world.insert_resource(GameEventReplay {
+        events: recording.events.iter().cloned().collect(),
+        start_tick,
+        ticks: recording.ticks,
+        end_state_hash: recording.end_state_hash,
+    });
+}
+
+/// Whether the replay reached the tick its recording ended with
+pub fn is_replay_finished(world: &World) -> bool {
+    let tick = world.resource::<SimulationClock>().tick;
+    world
+        .get_resource::<GameEventReplay>()
+        .is_some_and(|replay| tick - replay.start_tick >= replay.ticks)
+}
+
+/// Compares the current state of a finished replay to the state its recording ended in
+pub fn verify_replay(world: &mut World) -> Result<(), String> {
+    let expected_hash = world
+        .get_resource::<GameEventReplay>()
+        .ok_or("No recording is being replayed")?
+        .end_state_hash;
+
+    let hash = state_hash(world);
+    if hash == expected_hash {
+        Ok(())
+    } else {
+        Err(format!(
+            "The replay ended in state {:016x} instead of the recorded {:016x}",
+            hash, expected_hash
+        ))
+    }
+}
+
+/// Hash of everything the simulation keeps track of. It depends neither on entity ids nor on the
+/// order of entities, so a replay of a recorded game ends in the same hash.
+pub fn state_hash(world: &mut World) -> u64 {
+    let construction_locations: HashMap<Entity, Vec2> = world
+        .query::<(Entity, &Construction)>()
+        .iter(world)
+        .map(|(entity, construction)| (entity, construction.location))
+        .collect();
+
+    // Debug output of floating point numbers is exact, it can be parsed back to the same bits
+    let mut constructions: Vec<String> = world
+        .query::<(
+            &Construction,
+            Option<&EnergySatisfaction>,
+            Option<&ProductionCycle>,
+        )>()
+        .iter(world)
+        .filter(|(construction, _, _)| construction.status != ConstructionStatus::Hovering)
+        .map(|components| format!("{:?}", components))
+        .collect();
+    constructions.sort();
+
+    let mut connections: Vec<String> = world
+        .query::<&Connection>()
+        .iter(world)
+        .filter_map(|connection| {
+            let location1 = construction_locations.get(&connection.between().0)?;
+            let location2 = construction_locations.get(&connection.between().1)?;
+            let mut locations = [location1, location2];
+            locations.sort_by(|location1, location2| compare_locations(location1, location2));
+            Some(format!("{:?}", locations))
+        })
+        .collect();
+    connections.sort();
+
+    let mut deposits: Vec<String> = world
+        .query::<&Deposit>()
+        .iter(world)
+        .map(|deposit| format!("{:?}", deposit))
+        .collect();
+    deposits.sort();
+
+    let mut hasher = StateHasher::default();
+    hasher.write(&format!("{:?}", world.resource::<PlayerResources>()));
+    for part in constructions.iter().chain(&connections).chain(&deposits) {
+        hasher.write(part);
+    }
+    hasher.0
+}
+
+/// 64 bit FNV-1a hash. Unlike the hashers of the standard library, its algorithm is fixed, so
+/// recordings can be verified by any build of the game.
+struct StateHasher(u64);
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl StateHasher {
+    /// Each part is terminated by a byte that does not occur in text, so that the parts cannot
+    /// run into each other
+    fn write(&mut self, part: &str) {
+        for byte in part.bytes().chain(std::iter::once(0xff)) {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+/// Sends the events of the replayed recording that are due in the current tick. It runs before
+/// the game events are processed, so they are processed in the same tick as in the recorded game.
+pub(crate) fn replay_game_events_system(
+    clock: Res<SimulationClock>,
+    replay: Option<ResMut<GameEventReplay>>,
+    construction_query: Query<(Entity, &Construction)>,
+    connection_query: Query<(Entity, &Connection)>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    let mut replay = match replay {
+        Some(replay) => replay,
+        None => return,
+    };
+
+    let tick = clock.tick - replay.start_tick;
+    while replay
+        .events
+        .front()
+        .is_some_and(|recorded_event| recorded_event.tick <= tick)
+    {
+        let recorded_event = replay.events.pop_front().unwrap();
+        match replayed_event(
+            &recorded_event.event,
+            &construction_query,
+            &connection_query,
+        ) {
+            Some(event) => game_event_writer.send(event),
+            None => warn!(
+                "Cannot replay {:?} of tick {}, the game took a different course",
+                recorded_event.event, recorded_event.tick
+            ),
+        }
+    }
+}
+
+/// Writes down the game events that are processed in the current tick
+pub(crate) fn record_game_events_system(
+    clock: Res<SimulationClock>,
+    recorder: Option<ResMut<GameEventRecorder>>,
+    mut game_events: EventReader<GameEvent>,
+    construction_query: Query<(Entity, &Construction)>,
+    connection_query: Query<(Entity, &Connection)>,
+) {
+    // Events are read in any case, so that a recording does not start with events of the past
+    let events: Vec<_> = game_events.iter().collect();
+    let mut recorder = match recorder {
+        Some(recorder) => recorder,
+        None => return,
+    };
+
+    let tick = clock.tick - recorder.start_tick;
+    for event in events {
+        if let Some(event) = recorded_event(event, &construction_query, &connection_query) {
+            recorder
+                .recording
+                .events
+                .push(RecordedGameEvent { tick, event });
+        }
+    }
+}
+
+/// Replaces entities by locations. Rejections are not recorded, they are a result of the game.
+fn recorded_event(
+    event: &GameEvent,
+    construction_query: &Query<(Entity, &Construction)>,
+    connection_query: &Query<(Entity, &Connection)>,
+) -> Option<RecordedEvent> {
+    let location = |entity: Entity| {
+        construction_query
+            .get(entity)
+            .ok()
+            .map(|(_, construction)| construction.location)
+    };
+
+    match event {
+        GameEvent::SwitchToGameMode(game_mode) => {
+            Some(RecordedEvent::SwitchToGameMode(game_mode.clone()))
+        }
+        GameEvent::BuildConstruction(location, kind) => {
+            Some(RecordedEvent::BuildConstruction(*location, kind.clone()))
+        }
+        GameEvent::DemolishConstruction(entity) => {
+            Some(RecordedEvent::DemolishConstruction(location(*entity)?))
+        }
+        GameEvent::ConnectConstructions(entity1, entity2) => Some(
+            RecordedEvent::ConnectConstructions(location(*entity1)?, location(*entity2)?),
+        ),
+        GameEvent::CutConnection(entity) => {
+            let (_, connection) = connection_query.get(*entity).ok()?;
+            let (entity1, entity2) = *connection.between();
+            Some(RecordedEvent::CutConnection(
+                location(entity1)?,
+                location(entity2)?,
+            ))
+        }
+        GameEvent::BuildRejected { .. } => None,
+    }
+}
+
+/// Looks up the entities of a recorded event by their locations
+fn replayed_event(
+    event: &RecordedEvent,
+    construction_query: &Query<(Entity, &Construction)>,
+    connection_query: &Query<(Entity, &Connection)>,
+) -> Option<GameEvent> {
+    // The hovering construction of the build mode may be at the very same location
+    let construction_at = |location: &Vec2| {
+        construction_query
+            .iter()
+            .find(|(_, construction)| {
+                construction.location == *location
+                    && construction.status != ConstructionStatus::Hovering
+            })
+            .map(|(entity, _)| entity)
+    };
+
+    match event {
+        RecordedEvent::SwitchToGameMode(game_mode) => {
+            Some(GameEvent::SwitchToGameMode(game_mode.clone()))
+        }
+        RecordedEvent::BuildConstruction(location, kind) => {
+            Some(GameEvent::BuildConstruction(*location, kind.clone()))
+        }
+        RecordedEvent::DemolishConstruction(location) => {
+            Some(GameEvent::DemolishConstruction(construction_at(location)?))
+        }
+        RecordedEvent::ConnectConstructions(location1, location2) => {
+            Some(GameEvent::ConnectConstructions(
+                construction_at(location1)?,
+                construction_at(location2)?,
+            ))
+        }
+        RecordedEvent::CutConnection(location1, location2) => {
+            let entity1 = construction_at(location1)?;
+            let entity2 = construction_at(location2)?;
+            connection_query
+                .iter()
+                .find(|(_, connection)| {
+                    connection.connects_to(entity1) && connection.connects_to(entity2)
+                })
+                .map(|(entity, _)| GameEvent::CutConnection(entity))
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use bevy::prelude::*;
+use model::configuration_file;
+use model::connection::Connection;
+use model::construction::Construction;
+use model::game_configuration::GameConfiguration;
+use model::resources::PlayerResources;
+use model::scenario::Scenario;
+use model::simulation::SimulationClock;
+
+/// Starts the game with the layout of a scenario
+pub struct ScenarioPlugin {
+    pub scenario: Scenario,
+}
+
+impl Plugin for ScenarioPlugin {
+    fn build(&self, app: &mut App) {
+        // The configuration is overridden before any other startup system reads it
+        app.insert_resource(self.scenario.clone())
+            .add_startup_system_to_stage(StartupStage::PreStartup, apply_scenario_system)
+            .add_startup_system(spawn_scenario_system);
+    }
+}
+
+pub fn load_scenario(path: &Path) -> Result<Scenario, String> {
+    configuration_file::load_ron_file(path)
+}
+
+/// Spawns the constructions, connections, deposits and obstacles of a scenario
+pub fn spawn_scenario(commands: &mut Commands, scenario: &Scenario) {
+    let mut id_to_entity_map: HashMap<u32, Entity> = HashMap::new();
+    for scenario_construction in scenario.constructions.iter() {
+        let entity = commands
+            .spawn()
+            .insert(Construction {
+                location: scenario_construction.location,
+                kind: scenario_construction.kind.clone(),
+                status: scenario_construction.status.clone(),
+            })
+            .id();
+        id_to_entity_map.insert(scenario_construction.id, entity);
+    }
+
+    for (id1, id2) in scenario.connections.iter() {
+        match (id_to_entity_map.get(id1), id_to_entity_map.get(id2)) {
+            (Some(entity1), Some(entity2)) => {
+                commands
+                    .spawn()
+                    .insert(Connection::new_between(*entity1, *entity2));
+            }
+            _ => warn!(
+                "Skipping connection between unknown constructions {:?}",
+                (id1, id2)
+            ),
+        }
+    }
+
+    for deposit in scenario.deposits.iter() {
+        commands.spawn().insert(deposit.clone());
+    }
+    for obstacle in scenario.obstacles.iter() {
+        commands.spawn().insert(obstacle.clone());
+    }
+}
+
+/// Applies the configuration overrides and the starting material. A reloaded configuration file
+/// replaces the overrides.
+fn apply_scenario_system(
+    mut commands: Commands,
+    scenario: Res<Scenario>,
+    mut game_configuration: ResMut<GameConfiguration>,
+    mut clock: ResMut<SimulationClock>,
+) {
+    scenario.configuration.apply_to(&mut game_configuration);
+    clock.tick_duration = 1. / game_configuration.ticks_per_second;
+
+    if let Some(starting_material) = scenario.starting_material {
+        commands.insert_resource(PlayerResources {
+            material_available: starting_material,
+            material_rate_per_second: 0.,
+            energy_need: 0.,
+            energy_available: 0.,
+        });
+    }
+}
+
+fn spawn_scenario_system(mut commands: Commands, scenario: Res<Scenario>) {
+    spawn_scenario(&mut commands, &scenario);
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use bevy::prelude::*;
+use model::configuration_file;
+use model::connection::Connection;
+use model::construction::{
+    Construction, ConstructionStatus, EnergySatisfaction, Idle, MinedDeposit, ProductionCycle,
+    UnderConstructionMarker,
+};
+use model::game::GameMode;
+use model::game_configuration::GameConfiguration;
+use model::map::{Deposit, Obstacle};
+use model::resources::PlayerResources;
+use model::simulation::SimulationClock;
+use model::snapshot::{ConstructionSnapshot, GameSnapshot};
+use model::terrain::Terrain;
+use model::RemovalEvent;
+
+/// Captures the current state of the game
+pub fn take_snapshot(world: &mut World) -> GameSnapshot {
+    // Constructions refer to the deposit they mine by its index in the snapshot
+    let (deposit_entities, deposits): (Vec<Entity>, Vec<Deposit>) = world
+        .query::<(Entity, &Deposit)>()
+        .iter(world)
+        .map(|(entity, deposit)| (entity, deposit.clone()))
+        .unzip();
+    let constructions = world
+        .query::<(
+            Entity,
+            &Construction,
+            Option<&ProductionCycle>,
+            Option<&MinedDeposit>,
+            Option<&Idle>,
+        )>()
+        .iter(world)
+        .filter(|(_, construction, ..)| construction.status != ConstructionStatus::Hovering)
+        .map(
+            |(entity, construction, production_cycle, mined_deposit, idle)| ConstructionSnapshot {
+                id: entity.to_bits(),
+                construction: construction.clone(),
+                production_progress: production_cycle
+                    .map(|production_cycle| production_cycle.progress),
+                mined_deposit: mined_deposit.and_then(|mined_deposit| {
+                    deposit_entities
+                        .iter()
+                        .position(|entity| *entity == mined_deposit.0)
+                }),
+                idle: idle.is_some(),
+            },
+        )
+        .collect();
+    let connections = world
+        .query::<&Connection>()
+        .iter(world)
+        .map(|connection| {
+            let (entity1, entity2) = *connection.between();
+            (entity1.to_bits(), entity2.to_bits())
+        })
+        .collect();
+    let obstacles = world.query::<&Obstacle>().iter(world).cloned().collect();
+
+    GameSnapshot {
+        game_configuration: world.resource::<GameConfiguration>().clone(),
+        game_mode: world.resource::<GameMode>().clone(),
+        player_resources: world.resource::<PlayerResources>().clone(),
+        terrain: world.resource::<Terrain>().clone(),
+        constructions,
+        connections,
+        deposits,
+        obstacles,
+    }
+}
+
+/// Replaces the current state of the game by the one of the snapshot. Constructions and
+/// connections are spawned as new entities, their shapes follow through the usual systems.
+pub fn restore_snapshot(world: &mut World, snapshot: &GameSnapshot) {
+    remove_game_state(world);
+
+    world.insert_resource(snapshot.game_configuration.clone());
+    world.insert_resource(snapshot.game_mode.clone());
+    world.insert_resource(snapshot.player_resources.clone());
+    world.insert_resource(snapshot.terrain.clone());
+    world.resource_mut::<SimulationClock>().tick_duration =
+        1. / snapshot.game_configuration.ticks_per_second;
+
+    let deposit_entities: Vec<Entity> = snapshot
+        .deposits
+        .iter()
+        .map(|deposit| world.spawn().insert(deposit.clone()).id())
+        .collect();
+
+    // Extractors keep mining the deposit they mined before, instead of the closest one
+    let mut id_to_entity_map: HashMap<u64, Entity> = HashMap::new();
+    for construction_snapshot in snapshot.constructions.iter() {
+        let mut entity = world.spawn();
+        entity.insert(construction_snapshot.construction.clone());
+        if let Some(progress) = construction_snapshot.production_progress {
+            entity.insert(ProductionCycle { progress });
+        }
+        if let Some(deposit_entity) = construction_snapshot
+            .mined_deposit
+            .and_then(|index| deposit_entities.get(index))
+        {
+            entity.insert(MinedDeposit(*deposit_entity));
+        }
+        if construction_snapshot.idle {
+            entity.insert(Idle);
+        }
+        id_to_entity_map.insert(construction_snapshot.id, entity.id());
+    }
+
+    for (id1, id2) in snapshot.connections.iter() {
+        match (id_to_entity_map.get(id1), id_to_entity_map.get(id2)) {
+            (Some(entity1), Some(entity2)) => {
+                world
+                    .spawn()
+                    .insert(Connection::new_between(*entity1, *entity2));
+            }
+            _ => warn!(
+                "Skipping connection between unknown constructions {:?}",
+                (id1, id2)
+            ),
+        }
+    }
+
+    for obstacle in snapshot.obstacles.iter() {
+        world.spawn().insert(obstacle.clone());
+    }
+}
+
+/// Writes a snapshot of the current game to a RON file
+pub fn save_game(world: &mut World, path: &Path) -> Result<(), String> {
+    configuration_file::save_ron_file(path, &take_snapshot(world))
+}
+
+/// Replaces the current game by the snapshot in a RON file. If the file cannot be loaded, the
+/// current game is left untouched.
+pub fn load_game(world: &mut World, path: &Path) -> Result<(), String> {
+    let snapshot = configuration_file::load_ron_file::<GameSnapshot>(path)?;
+    restore_snapshot(world, &snapshot);
+    Ok(())
+}
+
+/// Constructions and connections lose their simulated components right away, so that the
+/// simulation does not see them anymore. Their entities are despawned by the usual removal
+/// events, which clean up their shapes as well. The hovering construction is left to the build
+/// mode.
+fn remove_game_state(world: &mut World) {
+    let constructions: Vec<(Entity, Construction)> = world
+        .query_filtered::<(Entity, &Construction), Without<UnderConstructionMarker>>()
+        .iter(world)
+        .map(|(entity, construction)| (entity, construction.clone()))
+        .collect();
+    for (entity, construction) in constructions {
+        world.entity_mut(entity).remove_bundle_intersection::<(
+            Construction,
+            EnergySatisfaction,
+            ProductionCycle,
+            MinedDeposit,
+            Idle,
+        )>();
+        world.send_event(RemovalEvent {
+            entity,
+            component: construction,
+        });
+    }
+
+    let connections: Vec<(Entity, Connection)> = world
+        .query::<(Entity, &Connection)>()
+        .iter(world)
+        .map(|(entity, connection)| (entity, connection.clone()))
+        .collect();
+    for (entity, connection) in connections {
+        world.entity_mut(entity).remove::<Connection>();
+        world.send_event(RemovalEvent {
+            entity,
+            component: connection,
+        });
+    }
+
+    // Deposits and obstacles carry their shapes themselves
+    let map_entities: Vec<Entity> = world
+        .query_filtered::<Entity, Or<(With<Deposit>, With<Obstacle>)>>()
+        .iter(world)
+        .collect();
+    for entity in map_entities {
+        world.despawn(entity);
+    }
+}
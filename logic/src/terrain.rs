@@ -0,0 +1,24 @@
+use model::construction::Construction;
+use model::construction_registry::ConstructionRegistry;
+use model::terrain::Terrain;
+
+/// Material per second a construction produces at its location if it receives all the energy it
+/// demands
+pub fn effective_material_output(
+    construction_registry: &ConstructionRegistry,
+    terrain: &Terrain,
+    construction: &Construction,
+) -> f64 {
+    construction_registry.material_output(&construction.kind)
+        * terrain.material_output_factor(construction.location)
+}
+
+/// Energy a construction supplies in its current status at its location
+pub fn effective_energy_output(
+    construction_registry: &ConstructionRegistry,
+    terrain: &Terrain,
+    construction: &Construction,
+) -> f64 {
+    construction_registry.construction_energy_output(construction)
+        * terrain.energy_output_factor(construction.location)
+}
@@ -0,0 +1,167 @@
+use std::collections::VecDeque;
+
+const EPSILON: f64 = 1e-9;
+
+#[derive(Debug)]
+struct Arc {
+    to: usize,
+    capacity: f64,
+    cost: f64,
+}
+
+/// Directed flow network solved with successive shortest paths. Every arc is stored together
+/// with its residual counterpart, so the reverse arc of `arc` is always found at `arc ^ 1`.
+#[derive(Debug)]
+pub struct FlowNetwork {
+    arcs: Vec<Arc>,
+    flows: Vec<f64>,
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl FlowNetwork {
+    pub fn new(node_count: usize) -> Self {
+        Self {
+            arcs: Vec::new(),
+            flows: Vec::new(),
+            outgoing: vec![Vec::new(); node_count],
+        }
+    }
+
+    /// Adds a directed arc and returns its index, which can later be passed to `flow`.
+    pub fn add_arc(&mut self, from: usize, to: usize, capacity: f64, cost: f64) -> usize {
+        let arc = self.arcs.len();
+
+        self.arcs.push(Arc { to, capacity, cost });
+        self.flows.push(0.);
+        self.outgoing[from].push(arc);
+
+        self.arcs.push(Arc {
+            to: from,
+            capacity: 0.,
+            cost: -cost,
+        });
+        self.flows.push(0.);
+        self.outgoing[to].push(arc + 1);
+
+        arc
+    }
+
+    pub fn flow(&self, arc: usize) -> f64 {
+        self.flows[arc]
+    }
+
+    /// Pushes as much flow as possible from `source` to `sink`, always augmenting along the
+    /// cheapest residual path first. Returns the total amount of flow sent.
+    pub fn min_cost_max_flow(&mut self, source: usize, sink: usize) -> f64 {
+        let mut total_flow = 0.;
+
+        loop {
+            let incoming = self.cheapest_path(source);
+            if incoming[sink].is_none() {
+                break;
+            }
+
+            let mut amount = f64::INFINITY;
+            let mut node = sink;
+            while let Some(arc) = incoming[node] {
+                amount = f64::min(amount, self.residual(arc));
+                node = self.arcs[arc ^ 1].to;
+            }
+
+            let mut node = sink;
+            while let Some(arc) = incoming[node] {
+                self.flows[arc] += amount;
+                self.flows[arc ^ 1] -= amount;
+                node = self.arcs[arc ^ 1].to;
+            }
+
+            total_flow += amount;
+        }
+
+        total_flow
+    }
+
+    fn residual(&self, arc: usize) -> f64 {
+        self.arcs[arc].capacity - self.flows[arc]
+    }
+
+    /// Queue based Bellman-Ford over the residual network. Returns for each node the arc over
+    /// which it is reached on the cheapest path from `source`.
+    fn cheapest_path(&self, source: usize) -> Vec<Option<usize>> {
+        let node_count = self.outgoing.len();
+        let mut distances = vec![f64::INFINITY; node_count];
+        let mut incoming = vec![None; node_count];
+        let mut queued = vec![false; node_count];
+        let mut queue = VecDeque::new();
+
+        distances[source] = 0.;
+        queue.push_back(source);
+
+        while let Some(node) = queue.pop_front() {
+            queued[node] = false;
+
+            for &arc in self.outgoing[node].iter() {
+                if self.residual(arc) <= EPSILON {
+                    continue;
+                }
+
+                let next = self.arcs[arc].to;
+                let distance = distances[node] + self.arcs[arc].cost;
+                if distance + EPSILON < distances[next] {
+                    distances[next] = distance;
+                    incoming[next] = Some(arc);
+                    if !queued[next] {
+                        queued[next] = true;
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        incoming
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// S -(2)- A -(1)- T
+    #[test]
+    fn test_min_cost_max_flow_saturates_capacity() {
+        let mut network = FlowNetwork::new(3);
+        let source_arc = network.add_arc(0, 1, 2., 1.);
+        let sink_arc = network.add_arc(1, 2, 1., 1.);
+
+        assert_eq!(1., network.min_cost_max_flow(0, 2));
+        assert_eq!(1., network.flow(source_arc));
+        assert_eq!(1., network.flow(sink_arc));
+    }
+
+    ///      / A \
+    /// S -+       +- C -(2)- T
+    ///      \ B /
+    #[test]
+    fn test_min_cost_max_flow_prefers_cheaper_path() {
+        let mut network = FlowNetwork::new(5);
+        let cheap_arc = network.add_arc(0, 1, 2., 1.);
+        network.add_arc(1, 3, 2., 1.);
+        let expensive_arc = network.add_arc(0, 2, 2., 5.);
+        network.add_arc(2, 3, 2., 5.);
+        network.add_arc(3, 4, 2., 0.);
+
+        assert_eq!(2., network.min_cost_max_flow(0, 4));
+        assert_eq!(2., network.flow(cheap_arc));
+        assert_eq!(0., network.flow(expensive_arc));
+    }
+
+    /// S -- A   T
+    #[test]
+    fn test_min_cost_max_flow_disconnected_sink() {
+        let mut network = FlowNetwork::new(3);
+        let arc = network.add_arc(0, 1, 1., 1.);
+
+        assert_eq!(0., network.min_cost_max_flow(0, 2));
+        assert_eq!(0., network.flow(arc));
+    }
+}
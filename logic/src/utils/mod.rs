@@ -1 +1 @@
-pub mod routing;
+pub mod min_cost_flow;
@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+use bevy_prototype_lyon::{
+    prelude::*,
+    shapes::{Circle, Rectangle, RectangleOrigin},
+};
+use model::map::{Deposit, Obstacle};
+use model::terrain::{Terrain, TileDecoration};
+
+/// Below everything else, including connections which are drawn at 0
+const Z_VALUE: f32 = -10.;
+const DECORATION_Z_VALUE: f32 = -9.;
+const OBSTACLE_Z_VALUE: f32 = 10.;
+const DEPOSIT_Z_VALUE: f32 = 11.;
+
+const OBSTACLE_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+const DEPOSIT_COLOR: Color = Color::rgb(0.8, 0.5, 0.2);
+const DEPOSIT_RADIUS: f32 = 6.;
+/// Share of the region color that remains for the border of each tile
+const TILE_BORDER_BRIGHTNESS: f32 = 0.8;
+const TILE_BORDER_WIDTH: f32 = 1.;
+
+pub struct MapPlugin;
+
+impl Plugin for MapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(redraw_map_system)
+            .add_system(spawn_obstacle_shape_system)
+            .add_system(spawn_deposit_shape_system);
+    }
+}
+
+#[derive(Component, Debug)]
+struct MapTile;
+
+/// Draws one tile per terrain tile underneath everything else, along with the decoration of its
+/// region, whenever the terrain changes
+fn redraw_map_system(
+    mut commands: Commands,
+    terrain: Res<Terrain>,
+    tile_query: Query<Entity, With<MapTile>>,
+) {
+    if !terrain.is_changed() {
+        return;
+    }
+
+    for tile_entity in tile_query.iter() {
+        commands.entity(tile_entity).despawn();
+    }
+
+    let tile_shape = Rectangle {
+        extents: Vec2::splat(terrain.tile_size),
+        origin: RectangleOrigin::Center,
+    };
+    for row in 0..terrain.rows {
+        for column in 0..terrain.columns {
+            let tile_center =
+                terrain.origin + (Vec2::new(column as f32, row as f32) + 0.5) * terrain.tile_size;
+            let region = match terrain.region_at(tile_center) {
+                Some(region) => region,
+                None => continue,
+            };
+
+            let [red, green, blue, alpha] = region.color;
+            let border_color = Color::rgba(
+                red * TILE_BORDER_BRIGHTNESS,
+                green * TILE_BORDER_BRIGHTNESS,
+                blue * TILE_BORDER_BRIGHTNESS,
+                alpha,
+            );
+            let bundle = GeometryBuilder::build_as(
+                &tile_shape,
+                DrawMode::Outlined {
+                    fill_mode: FillMode::color(Color::rgba(red, green, blue, alpha)),
+                    outline_mode: StrokeMode::new(border_color, TILE_BORDER_WIDTH),
+                },
+                Transform::from_translation(tile_center.extend(Z_VALUE)),
+            );
+            commands.spawn_bundle(bundle).insert(MapTile);
+
+            if let Some(decoration) = region.decoration.as_ref() {
+                spawn_tile_decoration(
+                    &mut commands,
+                    decoration,
+                    terrain.tile_size,
+                    column,
+                    row,
+                    tile_center,
+                );
+            }
+        }
+    }
+
+    debug!(
+        "Map drawn with {} x {} tiles",
+        terrain.columns, terrain.rows
+    );
+}
+
+/// Scatters the shapes of a decoration over a tile. Their locations only depend on the tile, so the
+/// map looks the same each time it is drawn.
+fn spawn_tile_decoration(
+    commands: &mut Commands,
+    decoration: &TileDecoration,
+    tile_size: f32,
+    column: usize,
+    row: usize,
+    tile_center: Vec2,
+) {
+    let [red, green, blue, alpha] = decoration.color;
+    let spread = (tile_size / 2. - decoration.radius).max(0.);
+    let mut seed = ((column as u64) << 32) | row as u64;
+    let mut next_offset = || {
+        seed = mix(seed);
+        (seed & 0xffff) as f32 / 0xffff as f32 * 2. - 1.
+    };
+
+    for _ in 0..decoration.count {
+        let offset = Vec2::new(next_offset(), next_offset()) * spread;
+        let decoration_circle = Circle {
+            radius: decoration.radius,
+            center: Vec2::ZERO,
+        };
+        commands
+            .spawn_bundle(GeometryBuilder::build_as(
+                &decoration_circle,
+                DrawMode::Fill(FillMode::color(Color::rgba(red, green, blue, alpha))),
+                Transform::from_translation((tile_center + offset).extend(DECORATION_Z_VALUE)),
+            ))
+            .insert(MapTile);
+    }
+}
+
+/// Scrambles the bits of a number (SplitMix64), good enough to scatter decorations
+fn mix(value: u64) -> u64 {
+    let mut value = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+fn spawn_obstacle_shape_system(
+    mut commands: Commands,
+    query: Query<(Entity, &Obstacle), Added<Obstacle>>,
+) {
+    for (entity, obstacle) in query.iter() {
+        let obstacle_circle = Circle {
+            radius: obstacle.radius,
+            center: Vec2::ZERO,
+        };
+        commands
+            .entity(entity)
+            .insert_bundle(GeometryBuilder::build_as(
+                &obstacle_circle,
+                DrawMode::Fill(FillMode::color(OBSTACLE_COLOR)),
+                Transform::from_translation(obstacle.location.extend(OBSTACLE_Z_VALUE)),
+            ));
+    }
+}
+
+fn spawn_deposit_shape_system(
+    mut commands: Commands,
+    query: Query<(Entity, &Deposit), Added<Deposit>>,
+) {
+    for (entity, deposit) in query.iter() {
+        let deposit_circle = Circle {
+            radius: DEPOSIT_RADIUS,
+            center: Vec2::ZERO,
+        };
+        commands
+            .entity(entity)
+            .insert_bundle(GeometryBuilder::build_as(
+                &deposit_circle,
+                DrawMode::Fill(FillMode::color(DEPOSIT_COLOR)),
+                Transform::from_translation(deposit.location.extend(DEPOSIT_Z_VALUE)),
+            ));
+    }
+}
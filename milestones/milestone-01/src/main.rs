@@ -1,21 +1,38 @@
 use std::f64::consts::PI;
 
+use bevy::asset::{AssetServerSettings, FileAssetIo};
 use bevy::prelude::*;
 use game::GamePlugin;
+use logic::scenario::{self, ScenarioPlugin};
 use model::{
     connection::Connection,
-    construction::{Construction, ConstructionKind, ConstructionStatus},
+    construction::{Construction, ConstructionStatus},
     RemovalEvent,
 };
 
+const ASSET_FOLDER: &str = "../../assets";
+const SCENARIO_FILE: &str = "scenarios/milestone-01.ron";
+/// Kind of the construction of the scenario that moves and that the blinker connects to
+const MOVEMENT_KIND: &str = "Base";
+
 struct BlinkerEntity(Option<Entity>);
-struct MovementEntity(Entity);
 
 fn main() {
+    let scenario_path = FileAssetIo::get_base_path()
+        .join(ASSET_FOLDER)
+        .join(SCENARIO_FILE);
+    let scenario =
+        scenario::load_scenario(&scenario_path).unwrap_or_else(|error| panic!("{}", error));
+
     App::new()
         .insert_resource(Msaa { samples: 4 })
+        .insert_resource(AssetServerSettings {
+            asset_folder: ASSET_FOLDER.to_string(),
+            watch_for_changes: false,
+        })
         .add_plugins(DefaultPlugins)
         .add_plugin(GamePlugin)
+        .add_plugin(ScenarioPlugin { scenario })
         .add_startup_system(init_system)
         .add_system(demo_movement_system)
         .add_system(demo_blinker_system)
@@ -23,54 +40,32 @@ fn main() {
 }
 
 fn init_system(mut commands: Commands) {
-    let movement_entity = commands
-        .spawn()
-        .insert(Construction {
-            location: Vec2::new(0., 0.),
-            kind: ConstructionKind::Base,
-            status: ConstructionStatus::Operating,
-        })
-        .id();
-    commands.insert_resource(MovementEntity(movement_entity));
-
-    let static_entity = commands
-        .spawn()
-        .insert(Construction {
-            location: Vec2::new(0., -100.),
-            kind: ConstructionKind::Collector,
-            status: ConstructionStatus::Operating,
-        })
-        .id();
-    commands
-        .spawn()
-        .insert(Connection::new_between(movement_entity, static_entity));
-
     commands.insert_resource(BlinkerEntity(None));
 }
 
-fn demo_movement_system(
-    time: Res<Time>,
-    movement_entity: Res<MovementEntity>,
-    mut query: Query<&mut Construction>,
-) {
+fn demo_movement_system(time: Res<Time>, mut query: Query<&mut Construction>) {
     let x = (time.seconds_since_startup() * PI).cos() * 200.;
-    query.get_mut(movement_entity.0).unwrap().location = Vec2::new(x as f32, 0.);
+    if let Some(mut construction) = query
+        .iter_mut()
+        .find(|construction| construction.kind == MOVEMENT_KIND.into())
+    {
+        construction.location = Vec2::new(x as f32, 0.);
+    }
 }
 
 fn demo_blinker_system(
     mut commands: Commands,
     time: Res<Time>,
     mut blinker_entity: ResMut<BlinkerEntity>,
-    movement_entity: Res<MovementEntity>,
     mut event_writer: EventWriter<RemovalEvent<Construction>>,
-    query: Query<&Construction>,
+    query: Query<(Entity, &Construction)>,
 ) {
     if time.seconds_since_startup() as i32 % 2 == 0 {
         // Do not show the blinker
         if let Some(entity) = blinker_entity.0 {
             blinker_entity.0 = None;
 
-            let construction = query.get(entity).unwrap();
+            let (_, construction) = query.get(entity).unwrap();
             event_writer.send(RemovalEvent {
                 entity: entity,
                 component: construction.clone(),
@@ -79,11 +74,19 @@ fn demo_blinker_system(
     } else {
         // Show the blinker
         if let None = blinker_entity.0 {
+            let movement_entity = match query
+                .iter()
+                .find(|(_, construction)| construction.kind == MOVEMENT_KIND.into())
+            {
+                Some((entity, _)) => entity,
+                None => return,
+            };
+
             let entity = commands
                 .spawn()
                 .insert(Construction {
                     location: Vec2::new(50., 100.),
-                    kind: ConstructionKind::Extractor,
+                    kind: "Extractor".into(),
                     status: ConstructionStatus::Operating,
                 })
                 .id();
@@ -91,7 +94,7 @@ fn demo_blinker_system(
 
             commands
                 .spawn()
-                .insert(Connection::new_between(entity, movement_entity.0));
+                .insert(Connection::new_between(entity, movement_entity));
         }
     }
 }
@@ -1,74 +1,26 @@
-use bevy::asset::AssetServerSettings;
+use bevy::asset::{AssetServerSettings, FileAssetIo};
 use bevy::prelude::*;
 use game::GamePlugin;
-use model::{
-    connection::Connection,
-    construction::{Construction, ConstructionKind, ConstructionStatus},
-};
+use logic::scenario::{self, ScenarioPlugin};
+
+const ASSET_FOLDER: &str = "../../assets";
+const SCENARIO_FILE: &str = "scenarios/milestone-02.ron";
 
 fn main() {
+    let scenario_path = FileAssetIo::get_base_path()
+        .join(ASSET_FOLDER)
+        .join(SCENARIO_FILE);
+    let scenario =
+        scenario::load_scenario(&scenario_path).unwrap_or_else(|error| panic!("{}", error));
+
     App::new()
         .insert_resource(Msaa { samples: 4 })
         .insert_resource(AssetServerSettings {
-            asset_folder: "../../assets".to_string(),
+            asset_folder: ASSET_FOLDER.to_string(),
             watch_for_changes: false,
         })
         .add_plugins(DefaultPlugins)
         .add_plugin(GamePlugin)
-        .add_startup_system(init_game_system)
+        .add_plugin(ScenarioPlugin { scenario })
         .run();
 }
-
-fn init_game_system(mut commands: Commands) {
-    let base_construction_entity = commands
-        .spawn()
-        .insert(Construction {
-            location: Vec2::new(0., 0.),
-            kind: ConstructionKind::Base,
-            status: ConstructionStatus::Operating,
-        })
-        .id();
-
-    let collector1_construction_entity = commands
-        .spawn()
-        .insert(Construction {
-            location: Vec2::new(0., 125.),
-            kind: ConstructionKind::Collector,
-            status: ConstructionStatus::Operating,
-        })
-        .id();
-    commands.spawn().insert(Connection::new_between(
-        base_construction_entity.clone(),
-        collector1_construction_entity.clone(),
-    ));
-
-    let collector2_construction_entity = commands
-        .spawn()
-        .insert(Construction {
-            location: Vec2::new(-100., 175.),
-            kind: ConstructionKind::Extractor,
-            status: ConstructionStatus::Operating,
-        })
-        .id();
-    commands.spawn().insert(Connection::new_between(
-        base_construction_entity.clone(),
-        collector2_construction_entity.clone(),
-    ));
-    commands.spawn().insert(Connection::new_between(
-        collector1_construction_entity.clone(),
-        collector2_construction_entity.clone(),
-    ));
-
-    let collector3_construction_entity = commands
-        .spawn()
-        .insert(Construction {
-            location: Vec2::new(200., -75.),
-            kind: ConstructionKind::Extractor,
-            status: ConstructionStatus::Operating,
-        })
-        .id();
-    commands.spawn().insert(Connection::new_between(
-        base_construction_entity.clone(),
-        collector3_construction_entity.clone(),
-    ));
-}
@@ -1,7 +1,7 @@
 use bevy::asset::AssetServerSettings;
 use bevy::prelude::*;
 use game::GamePlugin;
-use model::construction::{Construction, ConstructionKind, ConstructionStatus};
+use model::construction::{Construction, ConstructionStatus};
 
 fn main() {
     App::new()
@@ -26,7 +26,7 @@ fn main() {
 fn init_game_system(mut commands: Commands) {
     commands.spawn().insert(Construction {
         location: Vec2::new(0., 0.),
-        kind: ConstructionKind::Base,
+        kind: "Base".into(),
         status: ConstructionStatus::Operating,
     });
 }
@@ -1,20 +1,22 @@
 use bevy::{asset::AssetServerSettings, prelude::*};
-use game::GamePlugin;
+use game::SimulationPlugin;
 use model::{
     connection::Connection,
-    construction::{Construction, ConstructionKind, ConstructionStatus},
+    construction::{Construction, ConstructionStatus},
     game::GameEvent,
+    simulation::SimulationClock,
 };
 
 #[test]
-fn disallow_crossing_connections() {
+pub fn disallow_crossing_connections() {
     let mut app = App::new();
     app.insert_resource(AssetServerSettings {
         asset_folder: "../../assets".to_string(),
         watch_for_changes: false,
     });
-    app.add_plugins(DefaultPlugins);
-    app.add_plugin(GamePlugin);
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
 
     // Spawn initital constructions
     let base_entity = app
@@ -22,7 +24,7 @@ fn disallow_crossing_connections() {
         .spawn()
         .insert(Construction {
             location: Vec2::new(0., 0.),
-            kind: ConstructionKind::Base,
+            kind: "Base".into(),
             status: ConstructionStatus::Operating,
         })
         .id();
@@ -30,8 +32,8 @@ fn disallow_crossing_connections() {
         .world
         .spawn()
         .insert(Construction {
-            location: Vec2::new(20., 50.),
-            kind: ConstructionKind::Extractor,
+            location: Vec2::new(100., 60.),
+            kind: "Extractor".into(),
             status: ConstructionStatus::Operating,
         })
         .id();
@@ -39,8 +41,8 @@ fn disallow_crossing_connections() {
         .world
         .spawn()
         .insert(Construction {
-            location: Vec2::new(20., -50.),
-            kind: ConstructionKind::Extractor,
+            location: Vec2::new(100., -60.),
+            kind: "Extractor".into(),
             status: ConstructionStatus::Operating,
         })
         .id();
@@ -61,10 +63,11 @@ fn disallow_crossing_connections() {
 
     app.update();
 
-    // Now, lets build a new construction and check the connections that are created
+    // Now, lets build a new construction and check the connections that are created. It has to be
+    // placed outside of the influence areas of the others, or the build is rejected.
     app.world.send_event(GameEvent::BuildConstruction(
-        Vec2::new(21., 0.),
-        ConstructionKind::Collector,
+        Vec2::new(140., 0.),
+        "Collector".into(),
     ));
     app.update();
 
@@ -75,3 +78,63 @@ fn disallow_crossing_connections() {
     // should not be a 6th connection to the base because this would cross an existing connection.
     assert_eq!(5, connection_query.iter(&app.world).len());
 }
+
+#[test]
+fn connect_and_cut_constructions_manually() {
+    let mut app = App::new();
+    app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        watch_for_changes: false,
+    });
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
+
+    // Spawn initital constructions: A square of constructions, without any connections
+    let spawn_construction = |app: &mut App, x: f32, y: f32| {
+        app.world
+            .spawn()
+            .insert(Construction {
+                location: Vec2::new(x, y),
+                kind: "Extractor".into(),
+                status: ConstructionStatus::Operating,
+            })
+            .id()
+    };
+    let bottom_left_entity = spawn_construction(&mut app, 0., 0.);
+    let top_right_entity = spawn_construction(&mut app, 80., 80.);
+    let top_left_entity = spawn_construction(&mut app, 0., 80.);
+    let bottom_right_entity = spawn_construction(&mut app, 80., 0.);
+    let far_away_entity = spawn_construction(&mut app, 1000., 0.);
+    app.update();
+
+    let mut connection_query = app.world.query::<(Entity, &Connection)>();
+
+    // A diagonal connection within the connection range of extractors is allowed
+    app.world.send_event(GameEvent::ConnectConstructions(
+        bottom_left_entity,
+        top_right_entity,
+    ));
+    app.update();
+    assert_eq!(1, connection_query.iter(&app.world).len());
+
+    // The other diagonal would cross the first one, and the far away construction is out of range
+    app.world.send_event(GameEvent::ConnectConstructions(
+        top_left_entity,
+        bottom_right_entity,
+    ));
+    app.world.send_event(GameEvent::ConnectConstructions(
+        bottom_right_entity,
+        far_away_entity,
+    ));
+    app.update();
+    assert_eq!(1, connection_query.iter(&app.world).len());
+
+    // Cutting the diagonal connection removes it again
+    let (connection_entity, _) = connection_query.iter(&app.world).next().unwrap();
+    app.world
+        .send_event(GameEvent::CutConnection(connection_entity));
+    app.update();
+    app.update();
+    assert_eq!(0, connection_query.iter(&app.world).len());
+}
@@ -1,8 +1,13 @@
-use bevy::{asset::AssetServerSettings, prelude::*};
-use game::GamePlugin;
+use bevy::{asset::AssetServerSettings, ecs::event::Events, prelude::*};
+use game::SimulationPlugin;
 use model::{
-    construction::{Construction, ConstructionKind, ConstructionStatus},
-    game::GameEvent,
+    collision::{CollisionEvent, CollisionStatus, Collisions},
+    connection::Connection,
+    construction::{Construction, ConstructionStatus, Idle},
+    game::{BuildRejectionReason, GameEvent, GameMode},
+    map::Deposit,
+    resources::PlayerResources,
+    simulation::SimulationClock,
 };
 
 #[test]
@@ -12,13 +17,14 @@ fn prevent_building_if_influence_areas_overlap() {
         asset_folder: "../../assets".to_string(),
         watch_for_changes: false,
     });
-    app.add_plugins(DefaultPlugins);
-    app.add_plugin(GamePlugin);
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
 
     // Spawn initital construction
     app.world.spawn().insert(Construction {
         location: Vec2::new(0., 0.),
-        kind: ConstructionKind::Base,
+        kind: "Base".into(),
         status: ConstructionStatus::Operating,
     });
     app.update();
@@ -26,7 +32,7 @@ fn prevent_building_if_influence_areas_overlap() {
     // Now, lets build a new construction which overlaps
     app.world.send_event(GameEvent::BuildConstruction(
         Vec2::new(1., 0.),
-        ConstructionKind::Collector,
+        "Collector".into(),
     ));
     app.update();
 
@@ -36,8 +42,411 @@ fn prevent_building_if_influence_areas_overlap() {
     // Now, lets build a new construction which does not overlap
     app.world.send_event(GameEvent::BuildConstruction(
         Vec2::new(100., 0.),
-        ConstructionKind::Collector,
+        "Collector".into(),
     ));
     app.update();
     assert_eq!(2, construction_query.iter(&app.world).len());
+
+    // Of two overlapping constructions built at once, only the first one is built
+    app.world.send_event(GameEvent::BuildConstruction(
+        Vec2::new(0., 100.),
+        "Collector".into(),
+    ));
+    app.world.send_event(GameEvent::BuildConstruction(
+        Vec2::new(10., 100.),
+        "Collector".into(),
+    ));
+    app.update();
+    assert_eq!(3, construction_query.iter(&app.world).len());
+}
+
+#[test]
+fn reject_building_without_enough_material() {
+    let mut app = App::new();
+    app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        watch_for_changes: false,
+    });
+    app.insert_resource(PlayerResources {
+        material_available: 25.,
+        material_rate_per_second: 0.,
+        energy_need: 0.,
+        energy_available: 0.,
+    });
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
+
+    // Spawn initital construction
+    app.world.spawn().insert(Construction {
+        location: Vec2::new(0., 0.),
+        kind: "Base".into(),
+        status: ConstructionStatus::Operating,
+    });
+    app.update();
+
+    // A collector and its connection to the base demand more material than available
+    app.world.send_event(GameEvent::BuildConstruction(
+        Vec2::new(100., 0.),
+        "Collector".into(),
+    ));
+    app.update();
+
+    let mut construction_query = app.world.query::<&Construction>();
+    assert_eq!(1, construction_query.iter(&app.world).len());
+
+    let game_events = app.world.resource::<Events<GameEvent>>();
+    let rejections: Vec<_> = game_events
+        .get_reader()
+        .iter(game_events)
+        .filter_map(|event| match event {
+            GameEvent::BuildRejected { reason, .. } => Some(reason.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        vec![BuildRejectionReason::InsufficientMaterial {
+            required: 35.,
+            available: 25.
+        }],
+        rejections
+    );
+    assert!(app.world.resource::<PlayerResources>().material_available >= 0.);
+
+    // An extractor is cheap enough, as long as it is built next to a deposit
+    app.world.spawn().insert(Deposit {
+        location: Vec2::new(110., 0.),
+        remaining: 100.,
+    });
+    app.world.send_event(GameEvent::BuildConstruction(
+        Vec2::new(100., 0.),
+        "Extractor".into(),
+    ));
+    app.update();
+    assert_eq!(2, construction_query.iter(&app.world).len());
+}
+
+#[test]
+fn demolish_construction_with_refund() {
+    let mut app = App::new();
+    app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        watch_for_changes: false,
+    });
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
+
+    // Spawn initital constructions
+    let base_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(0., 0.),
+            kind: "Base".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    let extractor_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(100., 0.),
+            kind: "Extractor".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    app.update();
+
+    // Entering the demolish mode while already in it keeps the mode
+    for _ in 0..2 {
+        app.world
+            .send_event(GameEvent::SwitchToGameMode(GameMode::Demolishing));
+        app.update();
+    }
+    assert_eq!(&GameMode::Demolishing, app.world.resource::<GameMode>());
+
+    let material_before = app.world.resource::<PlayerResources>().material_available;
+
+    // The base is protected from demolition
+    app.world
+        .send_event(GameEvent::DemolishConstruction(base_entity));
+    app.update();
+    app.update();
+
+    let mut construction_query = app.world.query::<&Construction>();
+    assert_eq!(2, construction_query.iter(&app.world).len());
+
+    // Demolishing the extractor refunds half of its material build demand
+    app.world
+        .send_event(GameEvent::DemolishConstruction(extractor_entity));
+    app.update();
+    app.update();
+
+    assert_eq!(1, construction_query.iter(&app.world).len());
+    assert_eq!(
+        material_before + 10.,
+        app.world.resource::<PlayerResources>().material_available
+    );
+}
+
+#[test]
+fn prevent_construction_to_overlap_with_connections() {
+    let mut app = App::new();
+    app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        watch_for_changes: false,
+    });
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
+
+    // Spawn initital constructions, connected by a long connection
+    let base_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(0., 0.),
+            kind: "Base".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    let extractor_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(300., 0.),
+            kind: "Extractor".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    let connection_entity = app
+        .world
+        .spawn()
+        .insert(Connection::new_between(base_entity, extractor_entity))
+        .id();
+    app.update();
+
+    // A hovering construction moved on top of the connection collides with it
+    let hovering_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(140., 100.),
+            kind: "Collector".into(),
+            status: ConstructionStatus::Hovering,
+        })
+        .id();
+    app.update();
+
+    app.world
+        .get_mut::<Construction>(hovering_entity)
+        .unwrap()
+        .location = Vec2::new(140., 10.);
+    app.update();
+
+    let collisions = app.world.get::<Collisions>(hovering_entity).unwrap();
+    assert_eq!(
+        vec![connection_entity],
+        collisions.0.iter().cloned().collect::<Vec<_>>()
+    );
+
+    // Moving it away from the connection ends the collision
+    app.world
+        .get_mut::<Construction>(hovering_entity)
+        .unwrap()
+        .location = Vec2::new(140., 100.);
+    app.update();
+
+    assert!(app
+        .world
+        .get::<Collisions>(hovering_entity)
+        .unwrap()
+        .0
+        .is_empty());
+    let collision_events = app.world.resource::<Events<CollisionEvent>>();
+    let mut collision_reader = collision_events.get_reader();
+    let connection_collisions: Vec<_> = collision_reader
+        .iter(collision_events)
+        .filter(|event| event.entities == (hovering_entity, connection_entity))
+        .map(|event| &event.status)
+        .collect();
+    assert_eq!(
+        vec![&CollisionStatus::Started, &CollisionStatus::Ended],
+        connection_collisions
+    );
+
+    // Building on top of the connection is rejected
+    app.world.send_event(GameEvent::BuildConstruction(
+        Vec2::new(140., 10.),
+        "Collector".into(),
+    ));
+    app.update();
+
+    let game_events = app.world.resource::<Events<GameEvent>>();
+    let rejections: Vec<_> = game_events
+        .get_reader()
+        .iter(game_events)
+        .filter_map(|event| match event {
+            GameEvent::BuildRejected { reason, .. } => Some(reason.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(vec![BuildRejectionReason::ConnectionOverlap], rejections);
+}
+
+#[test]
+fn connection_changes_update_collisions() {
+    let mut app = App::new();
+    app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        watch_for_changes: false,
+    });
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
+
+    let base_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(0., 0.),
+            kind: "Base".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    let extractor_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(300., 0.),
+            kind: "Extractor".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    let collector_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(140., 10.),
+            kind: "Collector".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    app.update();
+    assert!(app
+        .world
+        .get::<Collisions>(collector_entity)
+        .unwrap()
+        .0
+        .is_empty());
+
+    // A new connection next to the collector starts a collision
+    let connection_entity = app
+        .world
+        .spawn()
+        .insert(Connection::new_between(base_entity, extractor_entity))
+        .id();
+    app.update();
+
+    let collisions = app.world.get::<Collisions>(collector_entity).unwrap();
+    assert_eq!(
+        vec![connection_entity],
+        collisions.0.iter().cloned().collect::<Vec<_>>()
+    );
+
+    // Cutting the connection ends it
+    app.world
+        .send_event(GameEvent::CutConnection(connection_entity));
+    app.update();
+    app.update();
+
+    assert!(app
+        .world
+        .get::<Collisions>(collector_entity)
+        .unwrap()
+        .0
+        .is_empty());
+    let collision_events = app.world.resource::<Events<CollisionEvent>>();
+    let mut collision_reader = collision_events.get_reader();
+    let connection_collisions: Vec<_> = collision_reader
+        .iter(collision_events)
+        .filter(|event| event.entities == (collector_entity, connection_entity))
+        .map(|event| &event.status)
+        .collect();
+    assert_eq!(vec![&CollisionStatus::Ended], connection_collisions);
+}
+
+#[test]
+fn extractor_goes_idle_once_its_deposit_runs_dry() {
+    let mut app = App::new();
+    app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        watch_for_changes: false,
+    });
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
+
+    // The collector powers the extractor through the base, the deposit holds a single whole
+    // unit
+    let base_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(0., 0.),
+            kind: "Base".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    let collector_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(-100., 0.),
+            kind: "Collector".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    let extractor_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(100., 0.),
+            kind: "Extractor".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    app.world
+        .spawn()
+        .insert(Connection::new_between(base_entity, collector_entity));
+    app.world
+        .spawn()
+        .insert(Connection::new_between(base_entity, extractor_entity));
+    let deposit_entity = app
+        .world
+        .spawn()
+        .insert(Deposit {
+            location: Vec2::new(100., 0.),
+            remaining: 1.5,
+        })
+        .id();
+    app.update();
+    app.update();
+
+    assert_eq!(4., app.world.resource::<PlayerResources>().energy_need);
+    let material_before = app.world.resource::<PlayerResources>().material_available;
+
+    // Producing the unit leaves less than a whole unit, which drains the deposit. Afterwards the
+    // extractor neither produces nor draws energy anymore
+    for _ in 0..300 {
+        app.update();
+    }
+
+    assert!(app.world.get_entity(deposit_entity).is_none());
+    assert!(app.world.get::<Idle>(extractor_entity).is_some());
+    let player_resources = app.world.resource::<PlayerResources>();
+    assert_eq!(material_before + 1., player_resources.material_available);
+    assert_eq!(0., player_resources.material_rate_per_second);
+    assert_eq!(0., player_resources.energy_need);
 }
@@ -1,5 +1,8 @@
 mod connections;
 mod constructions;
+mod replay;
+mod simulation;
+mod snapshot;
 
 fn main() {
     connections::disallow_crossing_connections();
@@ -0,0 +1,107 @@
+use bevy::{asset::AssetServerSettings, prelude::*};
+use game::SimulationPlugin;
+use logic::replay;
+use model::{
+    connection::Connection,
+    construction::{Construction, ConstructionStatus},
+    game::GameEvent,
+    map::Deposit,
+    recording::GameRecording,
+    simulation::SimulationClock,
+};
+
+const TICKS: u64 = 600;
+
+#[test]
+fn replay_of_recorded_game_ends_in_recorded_state() {
+    let recording = record_game();
+    assert_eq!(TICKS, recording.ticks);
+    assert_eq!(4, recording.events.len());
+
+    // Each replay runs in an app of its own, none of them may take a different course
+    for _ in 0..3 {
+        assert_eq!(Ok(()), game::verify_recording(&recording));
+    }
+
+    let tampered_recording = GameRecording {
+        end_state_hash: recording.end_state_hash ^ 1,
+        ..recording
+    };
+    assert!(game::verify_recording(&tampered_recording).is_err());
+}
+
+fn record_game() -> GameRecording {
+    let mut app = App::new();
+    app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        watch_for_changes: false,
+    });
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
+
+    // The base does not produce energy, a collector powers the construction sites
+    let base_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(0., 0.),
+            kind: "Base".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    let powering_collector_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(-120., 0.),
+            kind: "Collector".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    app.world.spawn().insert(Connection::new_between(
+        base_entity,
+        powering_collector_entity,
+    ));
+    app.world.spawn().insert(Deposit {
+        location: Vec2::new(0., 110.),
+        remaining: 500.,
+    });
+    app.update();
+
+    replay::start_recording(&mut app.world);
+    for tick in 1..=TICKS {
+        match tick {
+            1 => app.world.send_event(GameEvent::BuildConstruction(
+                Vec2::new(120., 0.),
+                "Collector".into(),
+            )),
+            2 => app.world.send_event(GameEvent::BuildConstruction(
+                Vec2::new(0., 110.),
+                "Extractor".into(),
+            )),
+            200 => app.world.send_event(GameEvent::BuildConstruction(
+                Vec2::new(0., -120.),
+                "Collector".into(),
+            )),
+            400 => {
+                let collector_entity = construction_at(&mut app.world, Vec2::new(120., 0.));
+                app.world
+                    .send_event(GameEvent::DemolishConstruction(collector_entity));
+            }
+            _ => {}
+        }
+        app.update();
+    }
+
+    replay::finish_recording(&mut app.world).unwrap()
+}
+
+fn construction_at(world: &mut World, location: Vec2) -> Entity {
+    world
+        .query::<(Entity, &Construction)>()
+        .iter(world)
+        .find(|(_, construction)| construction.location == location)
+        .map(|(entity, _)| entity)
+        .unwrap()
+}
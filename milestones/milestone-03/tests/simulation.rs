@@ -0,0 +1,144 @@
+use bevy::{asset::AssetServerSettings, prelude::*};
+use game::SimulationPlugin;
+use model::{
+    connection::Connection,
+    construction::{Construction, ConstructionStatus, EnergySatisfaction, ProductionCycle},
+    game::GameEvent,
+    map::Deposit,
+    resources::PlayerResources,
+    simulation::SimulationClock,
+};
+
+const TICKS: u64 = 900;
+
+/// State a run of the simulation ended in
+struct SimulationOutcome {
+    player_resources: PlayerResources,
+    /// Debug output of each construction along with its simulated components, ordered by
+    /// location. Floating point numbers are written exactly.
+    constructions: Vec<String>,
+}
+
+#[test]
+fn same_game_events_lead_to_identical_state() {
+    let SimulationOutcome {
+        player_resources: first_run,
+        constructions: first_run_constructions,
+    } = run_simulation();
+    let SimulationOutcome {
+        player_resources: second_run,
+        constructions: second_run_constructions,
+    } = run_simulation();
+
+    // Make sure the game actually went on, i.e. the extractor was built and produces material
+    assert!(first_run.material_rate_per_second > 0.);
+    assert_eq!(5, first_run_constructions.len());
+
+    assert_eq!(
+        first_run.material_available.to_bits(),
+        second_run.material_available.to_bits()
+    );
+    assert_eq!(
+        first_run.material_rate_per_second.to_bits(),
+        second_run.material_rate_per_second.to_bits()
+    );
+    assert_eq!(
+        first_run.energy_need.to_bits(),
+        second_run.energy_need.to_bits()
+    );
+    assert_eq!(
+        first_run.energy_available.to_bits(),
+        second_run.energy_available.to_bits()
+    );
+
+    // Not only the totals match, every single construction ends up in the same state
+    assert_eq!(first_run_constructions, second_run_constructions);
+}
+
+/// Game events and the tick before which they are sent
+fn game_events() -> Vec<(u64, GameEvent)> {
+    vec![
+        (
+            1,
+            GameEvent::BuildConstruction(Vec2::new(120., 0.), "Collector".into()),
+        ),
+        (
+            2,
+            GameEvent::BuildConstruction(Vec2::new(0., 110.), "Extractor".into()),
+        ),
+        (
+            200,
+            GameEvent::BuildConstruction(Vec2::new(0., -120.), "Collector".into()),
+        ),
+    ]
+}
+
+fn run_simulation() -> SimulationOutcome {
+    let mut app = App::new();
+    app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        watch_for_changes: false,
+    });
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
+
+    // The base does not produce energy, a collector powers the construction sites
+    let base_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(0., 0.),
+            kind: "Base".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    let collector_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(-120., 0.),
+            kind: "Collector".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    app.world
+        .spawn()
+        .insert(Connection::new_between(base_entity, collector_entity));
+    app.world.spawn().insert(Deposit {
+        location: Vec2::new(0., 110.),
+        remaining: 500.,
+    });
+
+    let game_events = game_events();
+    for tick in 1..=TICKS {
+        game_events
+            .iter()
+            .filter(|(event_tick, _)| *event_tick == tick)
+            .for_each(|(_, event)| app.world.send_event(event.clone()));
+        app.update();
+    }
+    assert_eq!(TICKS, app.world.resource::<SimulationClock>().tick);
+
+    let mut constructions: Vec<_> = app
+        .world
+        .query::<(
+            &Construction,
+            Option<&EnergySatisfaction>,
+            Option<&ProductionCycle>,
+        )>()
+        .iter(&app.world)
+        .collect();
+    constructions.sort_by(|(construction1, ..), (construction2, ..)| {
+        logic::construction::compare_locations(&construction1.location, &construction2.location)
+    });
+    let constructions = constructions
+        .iter()
+        .map(|components| format!("{:?}", components))
+        .collect();
+
+    SimulationOutcome {
+        player_resources: app.world.remove_resource::<PlayerResources>().unwrap(),
+        constructions,
+    }
+}
@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use bevy::{asset::AssetServerSettings, prelude::*};
+use game::SimulationPlugin;
+use model::{
+    connection::Connection,
+    construction::{Construction, ConstructionStatus, MinedDeposit, ProductionCycle},
+    map::Deposit,
+    resources::PlayerResources,
+    simulation::SimulationClock,
+    terrain::Terrain,
+};
+
+#[test]
+fn save_and_load_game() {
+    let mut app = App::new();
+    app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        watch_for_changes: false,
+    });
+    app.insert_resource(PlayerResources {
+        material_available: 42.,
+        material_rate_per_second: 0.,
+        energy_need: 0.,
+        energy_available: 0.,
+    });
+    let regions =
+        model::configuration_file::load_ron_file::<Terrain>(Path::new("../../assets/terrain.ron"))
+            .unwrap()
+            .regions;
+    let terrain = logic::map_generation::generate_map(7, &regions).terrain;
+    app.insert_resource(terrain.clone());
+    app.add_plugins(MinimalPlugins);
+    app.insert_resource(SimulationClock::stepped(30.));
+    app.add_plugin(SimulationPlugin);
+
+    // Spawn the game to save
+    let base_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(0., 0.),
+            kind: "Base".into(),
+            status: ConstructionStatus::Operating,
+        })
+        .id();
+    let extractor_entity = app
+        .world
+        .spawn()
+        .insert(Construction {
+            location: Vec2::new(100., 0.),
+            kind: "Extractor".into(),
+            status: ConstructionStatus::Building { progress: 0.5 },
+        })
+        .id();
+    app.world
+        .spawn()
+        .insert(Connection::new_between(base_entity, extractor_entity));
+    let mined_deposit_entity = app
+        .world
+        .spawn()
+        .insert(Deposit {
+            location: Vec2::new(110., 0.),
+            remaining: 300.,
+        })
+        .id();
+    app.update();
+    assert_eq!(
+        Some(&MinedDeposit(mined_deposit_entity)),
+        app.world.get::<MinedDeposit>(extractor_entity)
+    );
+    // A closer deposit that shows up later does not change the deposit that is mined
+    app.world.spawn().insert(Deposit {
+        location: Vec2::new(100., 0.),
+        remaining: 50.,
+    });
+    app.world
+        .get_mut::<ProductionCycle>(extractor_entity)
+        .unwrap()
+        .progress = 0.75;
+
+    let path = std::env::temp_dir().join("milestone-03-save-and-load-game.ron");
+    logic::snapshot::save_game(&mut app.world, &path).unwrap();
+
+    // Load it into a game that already has a construction of its own, which is replaced
+    let mut other_app = App::new();
+    other_app.insert_resource(AssetServerSettings {
+        asset_folder: "../../assets".to_string(),
+        watch_for_changes: false,
+    });
+    other_app.add_plugins(MinimalPlugins);
+    other_app.insert_resource(SimulationClock::stepped(30.));
+    other_app.add_plugin(SimulationPlugin);
+    other_app.world.spawn().insert(Construction {
+        location: Vec2::new(500., 500.),
+        kind: "Collector".into(),
+        status: ConstructionStatus::Operating,
+    });
+    other_app.update();
+
+    logic::snapshot::load_game(&mut other_app.world, &path).unwrap();
+    other_app.update();
+    other_app.update();
+
+    let mut construction_query = other_app.world.query::<(Entity, &Construction)>();
+    let constructions: Vec<_> = construction_query
+        .iter(&other_app.world)
+        .map(|(entity, construction)| (entity, construction.clone()))
+        .collect();
+    assert_eq!(2, constructions.len(), "{:?}", constructions);
+    let (loaded_base_entity, _) = constructions
+        .iter()
+        .find(|(_, construction)| construction.kind == "Base".into())
+        .unwrap();
+    let (loaded_extractor_entity, loaded_extractor) = constructions
+        .iter()
+        .find(|(_, construction)| construction.kind == "Extractor".into())
+        .unwrap();
+    assert_eq!(Vec2::new(100., 0.), loaded_extractor.location);
+    assert_eq!(
+        Some(&ProductionCycle { progress: 0.75 }),
+        other_app
+            .world
+            .get::<ProductionCycle>(*loaded_extractor_entity)
+    );
+    assert_eq!(&terrain, other_app.world.resource::<Terrain>());
+
+    // The connection refers to the new entities of the constructions
+    let mut connection_query = other_app.world.query::<&Connection>();
+    let connections: Vec<_> = connection_query.iter(&other_app.world).collect();
+    assert_eq!(1, connections.len());
+    assert!(connections[0].connects_to(*loaded_base_entity));
+    assert!(connections[0].connects_to(*loaded_extractor_entity));
+
+    let mut deposit_query = other_app.world.query::<&Deposit>();
+    assert_eq!(2, deposit_query.iter(&other_app.world).len());
+    let loaded_mined_deposit = other_app
+        .world
+        .get::<MinedDeposit>(*loaded_extractor_entity)
+        .unwrap();
+    assert_eq!(
+        Vec2::new(110., 0.),
+        other_app
+            .world
+            .get::<Deposit>(loaded_mined_deposit.0)
+            .unwrap()
+            .location
+    );
+
+    assert_eq!(
+        42.,
+        other_app
+            .world
+            .resource::<PlayerResources>()
+            .material_available
+    );
+}
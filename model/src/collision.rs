@@ -15,5 +15,7 @@ pub enum CollisionStatus {
 #[derive(Component, Debug)]
 pub struct CollisionAware;
 
+/// Entities a construction collides with: Other constructions whose influence area overlaps its
+/// own, and connections passing through its influence area
 #[derive(Component, Debug)]
 pub struct Collisions(pub HashSet<Entity>);
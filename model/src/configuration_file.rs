@@ -0,0 +1,151 @@
+use std::fs;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Seconds between two checks whether a watched file was modified
+const WATCH_INTERVAL: f32 = 1.;
+
+/// Reads a resource from a RON file, failures are described in a readable message
+pub fn load_ron_file<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("Cannot read {}: {}", path.display(), error))?;
+    ron::from_str(&content).map_err(|error| format!("Cannot parse {}: {}", path.display(), error))
+}
+
+/// Writes a value to a RON file, failures are described in a readable message
+pub fn save_ron_file<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let content = ron::ser::to_string_pretty(value, PrettyConfig::default())
+        .map_err(|error| format!("Cannot serialize {}: {}", path.display(), error))?;
+    fs::write(path, content).map_err(|error| format!("Cannot write {}: {}", path.display(), error))
+}
+
+/// File a resource of type `T` was loaded from. Whenever the file is modified, the resource is
+/// replaced by the new file content.
+pub(crate) struct WatchedFile<T> {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    timer: Timer,
+    resource: PhantomData<T>,
+}
+
+impl<T> WatchedFile<T> {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        let modified = modification_time(&path);
+        Self {
+            path,
+            modified,
+            timer: Timer::from_seconds(WATCH_INTERVAL, true),
+            resource: PhantomData,
+        }
+    }
+}
+
+/// Loads the resource from `path` and starts watching the file. If the file cannot be loaded, the
+/// error is logged and `fallback` is used until the file is fixed.
+pub(crate) fn insert_watched_resource<T: DeserializeOwned + Send + Sync + 'static>(
+    app: &mut App,
+    path: PathBuf,
+    fallback: impl FnOnce() -> T,
+) {
+    let resource = load_ron_file(&path).unwrap_or_else(|error| {
+        error!("{} Falling back to the built-in values.", error);
+        fallback()
+    });
+
+    app.insert_resource(resource)
+        .insert_resource(WatchedFile::<T>::new(path))
+        .add_system(reload_watched_file_system::<T>);
+}
+
+/// Replaces the resource once its file was modified. A file that cannot be loaded is reported
+/// and leaves the current resource untouched.
+pub(crate) fn reload_watched_file_system<T: DeserializeOwned + Send + Sync + 'static>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut watched_file: ResMut<WatchedFile<T>>,
+) {
+    if !watched_file.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let modified = modification_time(&watched_file.path);
+    if modified == watched_file.modified {
+        return;
+    }
+    watched_file.modified = modified;
+
+    match load_ron_file::<T>(&watched_file.path) {
+        Ok(resource) => {
+            info!("Reloaded {}", watched_file.path.display());
+            commands.insert_resource(resource);
+        }
+        Err(error) => error!("{} Keeping the previous values.", error),
+    }
+}
+
+fn modification_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_configuration::GameConfiguration;
+    use crate::terrain::Terrain;
+
+    #[test]
+    fn test_load_ron_file_game_configuration() {
+        let game_configuration: GameConfiguration =
+            load_ron_file(Path::new("../assets/game_configuration.ron")).unwrap();
+        assert_eq!(GameConfiguration::default(), game_configuration);
+    }
+
+    /// The simulation clock would never tick
+    #[test]
+    fn test_parse_game_configuration_invalid_ticks_per_second() {
+        let content = fs::read_to_string("../assets/game_configuration.ron").unwrap();
+        let invalid_content = content.replace("ticks_per_second: 30.", "ticks_per_second: 0.");
+        assert_ne!(content, invalid_content);
+        assert!(ron::from_str::<GameConfiguration>(&invalid_content).is_err());
+    }
+
+    /// The center of the map is neutral, so that the milestones behave as before
+    #[test]
+    fn test_load_ron_file_terrain() {
+        let terrain: Terrain = load_ron_file(Path::new("../assets/terrain.ron")).unwrap();
+        assert_eq!(16 * 12, terrain.tiles.len());
+        assert_eq!(
+            Some("Plains"),
+            terrain
+                .region_at(Vec2::ZERO)
+                .map(|region| region.name.as_str())
+        );
+        assert_eq!(
+            Some("Rich ore"),
+            terrain
+                .region_at(Vec2::new(-750., -550.))
+                .map(|region| region.name.as_str())
+        );
+        assert_eq!(
+            Some(4),
+            terrain
+                .region_at(Vec2::ZERO)
+                .and_then(|region| region.decoration.as_ref())
+                .map(|decoration| decoration.count)
+        );
+    }
+
+    #[test]
+    fn test_load_ron_file_missing() {
+        let result = load_ron_file::<GameConfiguration>(Path::new("missing.ron"));
+        assert!(result.unwrap_err().starts_with("Cannot read missing.ron"));
+    }
+}
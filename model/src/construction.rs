@@ -1,21 +1,30 @@
 use std::default::Default;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum ConstructionKind {
-    Base,
-    Collector,
-    Extractor,
+/// Key of a construction definition in the `ConstructionRegistry`, e.g. `"Extractor"`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ConstructionKind(pub String);
+
+impl From<&str> for ConstructionKind {
+    fn from(kind: &str) -> Self {
+        Self(kind.to_string())
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ConstructionStatus {
     Hovering,
+    /// Being built, `progress` goes from 0 to 1 as energy reaches the construction site
+    Building {
+        progress: f32,
+    },
     Operating,
 }
 
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Construction {
     pub location: Vec2,
     pub kind: ConstructionKind,
@@ -25,11 +34,31 @@ pub struct Construction {
 #[derive(Component)]
 pub struct UnderConstructionMarker;
 
+/// Production of the next material unit of a material producing construction. `progress` goes
+/// from 0 to 1, once completed a whole unit of material is delivered.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct ProductionCycle {
+    pub progress: f32,
+}
+
+/// Deposit a construction drains while it produces material
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct MinedDeposit(pub Entity);
+
+/// Marks a construction that cannot produce anymore, e.g. because its deposit ran dry
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct Idle;
+
+/// Fraction of its energy demand that actually reaches a construction. Constructions without any
+/// demand are always fully satisfied.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct EnergySatisfaction(pub f64);
+
 impl Default for Construction {
     fn default() -> Self {
         Self {
             location: Default::default(),
-            kind: ConstructionKind::Base,
+            kind: "Base".into(),
             status: ConstructionStatus::Operating,
         }
     }
@@ -0,0 +1,166 @@
+use serde::Deserialize;
+
+use crate::construction::{Construction, ConstructionKind, ConstructionStatus};
+
+/// Everything that makes up a kind of construction
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ConstructionDefinition {
+    pub kind: ConstructionKind,
+    /// Name shown to the player
+    pub name: String,
+    pub buildable: bool,
+    pub demolishable: bool,
+    pub material_build_demand: f64,
+    /// Seconds it takes to build the construction if it receives all the energy it demands
+    pub build_duration: f32,
+    /// Energy the construction site draws while it is being built
+    pub energy_build_input: f64,
+    pub energy_output: f64,
+    pub energy_input: f64,
+    pub material_output: f64,
+    /// Whether the construction has to be built next to a deposit, which it then drains
+    #[serde(default)]
+    pub requires_deposit: bool,
+    /// Whether the network of the player starts at the construction, e.g. the base. Everything
+    /// else has to be built within reach of such a network.
+    #[serde(default)]
+    pub network_root: bool,
+    pub influence_radius: f32,
+    /// Maximum length of the connections of the construction, see `ConnectionRangeRule`
+    pub connection_range: f32,
+    /// Red, green, blue and alpha, each between 0 and 1
+    pub color: [f32; 4],
+}
+
+/// All known kinds of constructions, in the order of the definitions file. Lookups of unknown
+/// kinds fall back to a construction that neither produces nor consumes anything.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct ConstructionRegistry {
+    constructions: Vec<ConstructionDefinition>,
+}
+
+impl ConstructionRegistry {
+    pub fn new(constructions: Vec<ConstructionDefinition>) -> Self {
+        Self { constructions }
+    }
+
+    pub fn from_ron(ron: &str) -> Result<Self, ron::Error> {
+        ron::from_str(ron)
+    }
+
+    /// Definitions shipped with the game, used whenever the definitions file cannot be loaded.
+    /// They are compiled in, so failing to parse them is a bug.
+    pub fn built_in() -> Self {
+        Self::from_ron(include_str!("../../assets/constructions.ron"))
+            .expect("built-in construction definitions should be valid")
+    }
+
+    pub fn get(&self, kind: &ConstructionKind) -> Option<&ConstructionDefinition> {
+        self.constructions
+            .iter()
+            .find(|definition| definition.kind == *kind)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ConstructionDefinition> {
+        self.constructions.iter()
+    }
+
+    pub fn is_buildable(&self, kind: &ConstructionKind) -> bool {
+        self.get(kind)
+            .is_some_and(|definition| definition.buildable)
+    }
+
+    pub fn is_demolishable(&self, kind: &ConstructionKind) -> bool {
+        self.get(kind)
+            .is_some_and(|definition| definition.demolishable)
+    }
+
+    pub fn material_build_demand(&self, kind: &ConstructionKind) -> f64 {
+        self.get(kind)
+            .map_or(0., |definition| definition.material_build_demand)
+    }
+
+    pub fn material_output(&self, kind: &ConstructionKind) -> f64 {
+        self.get(kind)
+            .map_or(0., |definition| definition.material_output)
+    }
+
+    pub fn requires_deposit(&self, kind: &ConstructionKind) -> bool {
+        self.get(kind)
+            .is_some_and(|definition| definition.requires_deposit)
+    }
+
+    pub fn is_network_root(&self, kind: &ConstructionKind) -> bool {
+        self.get(kind)
+            .is_some_and(|definition| definition.network_root)
+    }
+
+    pub fn energy_output(&self, kind: &ConstructionKind) -> f64 {
+        self.get(kind)
+            .map_or(0., |definition| definition.energy_output)
+    }
+
+    pub fn energy_input(&self, kind: &ConstructionKind) -> f64 {
+        self.get(kind)
+            .map_or(0., |definition| definition.energy_input)
+    }
+
+    /// Energy output of a construction in its current status
+    pub fn construction_energy_output(&self, construction: &Construction) -> f64 {
+        match construction.status {
+            ConstructionStatus::Building { .. } => 0.,
+            _ => self.energy_output(&construction.kind),
+        }
+    }
+
+    /// Energy input of a construction in its current status
+    pub fn construction_energy_input(&self, construction: &Construction) -> f64 {
+        match construction.status {
+            ConstructionStatus::Building { .. } => self
+                .get(&construction.kind)
+                .map_or(0., |definition| definition.energy_build_input),
+            _ => self.energy_input(&construction.kind),
+        }
+    }
+
+    /// Seconds it takes to build a construction if it receives all the energy it demands
+    pub fn build_duration(&self, kind: &ConstructionKind) -> f32 {
+        self.get(kind)
+            .map_or(0., |definition| definition.build_duration)
+    }
+
+    pub fn influence_radius(&self, kind: &ConstructionKind) -> f32 {
+        self.get(kind)
+            .map_or(0., |definition| definition.influence_radius)
+    }
+
+    pub fn connection_range(&self, kind: &ConstructionKind) -> f32 {
+        self.get(kind)
+            .map_or(0., |definition| definition.connection_range)
+    }
+
+    pub fn color(&self, kind: &ConstructionKind) -> [f32; 4] {
+        self.get(kind)
+            .map_or([0.5, 0.5, 0.5, 1.], |definition| definition.color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ron_constructions_file() {
+        let registry =
+            ConstructionRegistry::from_ron(include_str!("../../assets/constructions.ron"))
+                .expect("constructions.ron should be valid");
+
+        assert!(!registry.is_buildable(&"Base".into()));
+        assert!(registry.is_buildable(&"Extractor".into()));
+        assert!(registry.is_network_root(&"Base".into()));
+        assert!(!registry.is_network_root(&"Collector".into()));
+        assert_eq!(0.2, registry.material_output(&"Extractor".into()));
+        assert_eq!(120., registry.connection_range(&"Extractor".into()));
+        assert_eq!(0., registry.energy_output(&"Unknown".into()));
+    }
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+
+/// A set of constructions that are linked to each other by connections. Energy can only flow
+/// within a network, so supply and demand are balanced for each network on its own.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnergyNetwork {
+    pub constructions: Vec<Entity>,
+    pub energy_supply: f64,
+    pub energy_demand: f64,
+}
+
+impl EnergyNetwork {
+    /// Fraction of the demand that can be satisfied by the supply of this network:
+    /// ```
+    /// # use model::energy_network::EnergyNetwork;
+    /// let network = EnergyNetwork {
+    ///     constructions: vec![],
+    ///     energy_supply: 2.,
+    ///     energy_demand: 4.,
+    /// };
+    /// assert_eq!(0.5, network.satisfaction());
+    /// ```
+    pub fn satisfaction(&self) -> f64 {
+        if self.energy_demand > 0. {
+            f64::min(1., self.energy_supply / self.energy_demand)
+        } else {
+            1.
+        }
+    }
+
+    pub fn is_starved(&self) -> bool {
+        self.satisfaction() < 1.
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.constructions.contains(&entity)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct EnergyNetworks(pub Vec<EnergyNetwork>);
+
+impl EnergyNetworks {
+    pub fn network_of(&self, entity: Entity) -> Option<&EnergyNetwork> {
+        self.0.iter().find(|network| network.contains(entity))
+    }
+
+    pub fn starved(&self) -> impl Iterator<Item = &EnergyNetwork> {
+        self.0.iter().filter(|network| network.is_starved())
+    }
+}
@@ -1,11 +1,17 @@
+use std::fmt;
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::construction::ConstructionKind;
 
-#[derive(Component, Debug, Clone, PartialEq)]
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum GameMode {
     Idle,
     Building(ConstructionKind),
+    Demolishing,
+    Connecting,
+    Disconnecting,
 }
 
 pub(crate) fn game_mode_debug_system(game_mode: Res<GameMode>) {
@@ -18,4 +24,55 @@ pub(crate) fn game_mode_debug_system(game_mode: Res<GameMode>) {
 pub enum GameEvent {
     SwitchToGameMode(GameMode),
     BuildConstruction(Vec2, ConstructionKind),
+    DemolishConstruction(Entity),
+    ConnectConstructions(Entity, Entity),
+    CutConnection(Entity),
+    BuildRejected {
+        location: Vec2,
+        kind: ConstructionKind,
+        reason: BuildRejectionReason,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildRejectionReason {
+    NotBuildable,
+    InsufficientMaterial { required: f64, available: f64 },
+    InfluenceOverlap,
+    ObstacleOverlap,
+    ConnectionOverlap,
+    NoDeposit,
+    OutOfNetworkReach,
+}
+
+/// Why the hovering construction of the build mode cannot be built at its current location, if
+/// it cannot be built there at all
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct PlacementRejection(pub Option<BuildRejectionReason>);
+
+impl fmt::Display for BuildRejectionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildRejectionReason::NotBuildable => write!(f, "This construction cannot be built"),
+            BuildRejectionReason::InsufficientMaterial {
+                required,
+                available,
+            } => write!(
+                f,
+                "Not enough material ({:.0} required, {:.0} available)",
+                required, available
+            ),
+            BuildRejectionReason::InfluenceOverlap => {
+                write!(f, "Overlaps with another construction")
+            }
+            BuildRejectionReason::ObstacleOverlap => write!(f, "Blocked by an obstacle"),
+            BuildRejectionReason::ConnectionOverlap => write!(f, "Overlaps with a connection"),
+            BuildRejectionReason::NoDeposit => {
+                write!(f, "Needs a deposit within its influence area")
+            }
+            BuildRejectionReason::OutOfNetworkReach => {
+                write!(f, "Cannot be connected to the network of the base")
+            }
+        }
+    }
 }
@@ -1,55 +1,82 @@
-use crate::construction::ConstructionKind;
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameConfiguration {
-    pub energy_output_base: f64,
-    pub energy_output_collector: f64,
-    pub energy_output_extractor: f64,
+    /// Material the player starts with
+    pub starting_material: f64,
 
-    pub energy_input_base: f64,
-    pub energy_input_collector: f64,
-    pub energy_input_extractor: f64,
+    /// Maximum amount of energy a single connection can carry
+    pub connection_capacity: f64,
+
+    /// How the connection ranges of two constructions limit the length of a connection between
+    /// them
+    pub connection_range_rule: ConnectionRangeRule,
+
+    /// Material a connection costs per unit of its length
+    pub connection_material_cost: f64,
+
+    /// Share of the material build demand that is refunded when demolishing a construction
+    pub demolition_refund: f64,
+
+    /// Number of simulation ticks per second of game time, has to be positive
+    #[serde(deserialize_with = "deserialize_ticks_per_second")]
+    pub ticks_per_second: f64,
+
+    /// Smallest camera scale, i.e. how far the player can zoom in
+    pub zoom_scale_min: f32,
+
+    /// Largest camera scale, i.e. how far the player can zoom out
+    pub zoom_scale_max: f32,
 }
 
-impl GameConfiguration {
-    pub fn material_build_demand(&self, kind: &ConstructionKind) -> f64 {
-        match kind {
-            ConstructionKind::Base => f64::MAX,
-            ConstructionKind::Collector => 30.,
-            ConstructionKind::Extractor => 20.,
-        }
+/// A tick rate of zero or less would never let the simulation clock tick
+fn validate_ticks_per_second(ticks_per_second: f64) -> Result<f64, String> {
+    if ticks_per_second.is_finite() && ticks_per_second > 0. {
+        Ok(ticks_per_second)
+    } else {
+        Err(format!(
+            "ticks_per_second has to be a positive number, not {}",
+            ticks_per_second
+        ))
     }
+}
 
-    pub fn material_output(&self, kind: &ConstructionKind) -> f64 {
-        match kind {
-            ConstructionKind::Base => 0.,
-            ConstructionKind::Collector => 0.,
-            ConstructionKind::Extractor => 0.2,
-        }
-    }
-    pub fn energy_output(&self, kind: &ConstructionKind) -> f64 {
-        match kind {
-            ConstructionKind::Base => self.energy_output_base,
-            ConstructionKind::Collector => self.energy_output_collector,
-            ConstructionKind::Extractor => self.energy_output_extractor,
-        }
-    }
-    pub fn energy_input(&self, kind: &ConstructionKind) -> f64 {
-        match kind {
-            ConstructionKind::Base => self.energy_input_base,
-            ConstructionKind::Collector => self.energy_input_collector,
-            ConstructionKind::Extractor => self.energy_input_extractor,
-        }
-    }
+fn deserialize_ticks_per_second<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<f64, D::Error> {
+    validate_ticks_per_second(f64::deserialize(deserializer)?).map_err(D::Error::custom)
+}
 
-    pub fn influence_radius(&self, kind: &ConstructionKind) -> f32 {
-        match kind {
-            ConstructionKind::Base => 70.,
-            ConstructionKind::Collector => 30.,
-            ConstructionKind::Extractor => 20.,
-        }
-    }
+pub(crate) fn deserialize_optional_ticks_per_second<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    Option::<f64>::deserialize(deserializer)?
+        .map(validate_ticks_per_second)
+        .transpose()
+        .map_err(D::Error::custom)
+}
 
-    pub fn max_connection_distance(&self) -> f32 {
-        150.
+/// How the connection ranges of two construction kinds combine into the maximum length of a
+/// connection between them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConnectionRangeRule {
+    /// The smaller of both ranges
+    Minimum,
+    /// Both ranges added up
+    Sum,
+}
+
+impl Default for GameConfiguration {
+    fn default() -> Self {
+        Self {
+            starting_material: 100.,
+            connection_capacity: 10.,
+            connection_range_rule: ConnectionRangeRule::Minimum,
+            connection_material_cost: 0.05,
+            demolition_refund: 0.5,
+            ticks_per_second: 30.,
+            zoom_scale_min: 0.25,
+            zoom_scale_max: 4.,
+        }
     }
 }
@@ -1,15 +1,32 @@
-use bevy::prelude::{App, Entity, Plugin};
+use std::path::PathBuf;
+
+use bevy::asset::{AssetServerSettings, FileAssetIo};
+use bevy::prelude::{App, CoreStage, Entity, Plugin, SystemStage};
 use collision::CollisionEvent;
 use connection::Connection;
 use construction::Construction;
+use construction_registry::ConstructionRegistry;
+use energy_network::EnergyNetworks;
 use game::{GameEvent, GameMode};
+use game_configuration::GameConfiguration;
+use simulation::{SimulationApp, SimulationClock, SimulationStage};
+use terrain::Terrain;
 
 pub mod collision;
+pub mod configuration_file;
 pub mod connection;
 pub mod construction;
+pub mod construction_registry;
+pub mod energy_network;
 pub mod game;
 pub mod game_configuration;
+pub mod map;
+pub mod recording;
 pub mod resources;
+pub mod scenario;
+pub mod simulation;
+pub mod snapshot;
+pub mod terrain;
 
 #[derive(Debug)]
 pub struct RemovalEvent<T> {
@@ -17,26 +34,61 @@ pub struct RemovalEvent<T> {
     pub component: T,
 }
 
+const GAME_CONFIGURATION_FILE: &str = "game_configuration.ron";
+const CONSTRUCTIONS_FILE: &str = "constructions.ron";
+const TERRAIN_FILE: &str = "terrain.ron";
+
 pub struct ModelPlugin;
 
 impl Plugin for ModelPlugin {
     fn build(&self, app: &mut App) {
-        let game_config = game_configuration::GameConfiguration {
-            energy_output_base: 0.,
-            energy_output_collector: 5.,
-            energy_output_extractor: 0.,
-
-            energy_input_base: 0.,
-            energy_input_collector: 0.,
-            energy_input_extractor: 4.,
-        };
-
-        app.add_event::<RemovalEvent<Construction>>()
-            .add_event::<RemovalEvent<Connection>>()
-            .add_event::<GameEvent>()
-            .add_event::<CollisionEvent>()
-            .insert_resource(game_config)
-            .insert_resource(GameMode::Idle)
-            .add_system(game::game_mode_debug_system);
+        // Resources inserted up front, e.g. by a test, take precedence over the asset files
+        if !app.world.contains_resource::<GameConfiguration>() {
+            let path = asset_path(app, GAME_CONFIGURATION_FILE);
+            configuration_file::insert_watched_resource(app, path, GameConfiguration::default);
+        }
+        if !app.world.contains_resource::<ConstructionRegistry>() {
+            let path = asset_path(app, CONSTRUCTIONS_FILE);
+            configuration_file::insert_watched_resource(app, path, ConstructionRegistry::built_in);
+        }
+        if !app.world.contains_resource::<Terrain>() {
+            let path = asset_path(app, TERRAIN_FILE);
+            configuration_file::insert_watched_resource(app, path, Terrain::default);
+        }
+
+        // The tick rate is only read on startup, a reloaded configuration keeps the running clock
+        if !app.world.contains_resource::<SimulationClock>() {
+            let ticks_per_second = app.world.resource::<GameConfiguration>().ticks_per_second;
+            app.insert_resource(SimulationClock::new(ticks_per_second));
+        }
+
+        // Simulation systems run one after the other in the order they were added with
+        // `add_simulation_system`, so that every run of a tick has the same outcome
+        app.add_stage_after(
+            CoreStage::Update,
+            SimulationStage,
+            SystemStage::single_threaded()
+                .with_run_criteria(simulation::simulation_tick_run_criteria),
+        )
+        .add_simulation_event::<RemovalEvent<Construction>>()
+        .add_simulation_event::<RemovalEvent<Connection>>()
+        .add_simulation_event::<GameEvent>()
+        .add_simulation_event::<CollisionEvent>()
+        .insert_resource(GameMode::Idle)
+        .init_resource::<EnergyNetworks>()
+        .add_system(game::game_mode_debug_system);
     }
 }
+
+/// Location of a file in the asset folder, as configured by `AssetServerSettings`
+fn asset_path(app: &App, file_name: &str) -> PathBuf {
+    let asset_folder = app
+        .world
+        .get_resource::<AssetServerSettings>()
+        .map_or("assets".to_string(), |settings| {
+            settings.asset_folder.clone()
+        });
+    FileAssetIo::get_base_path()
+        .join(asset_folder)
+        .join(file_name)
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Finite amount of material in the ground that extractors can mine
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Deposit {
+    pub location: Vec2,
+    pub remaining: f64,
+}
+
+/// Area on the map nothing can be built on
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Obstacle {
+    pub location: Vec2,
+    pub radius: f32,
+}
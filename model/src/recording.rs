@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{construction::ConstructionKind, game::GameMode, snapshot::GameSnapshot};
+
+/// Game event as it is written to a recording. Constructions are referred to by their location,
+/// as a replay spawns its own entities. Connections are referred to by the locations of both
+/// their constructions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RecordedEvent {
+    SwitchToGameMode(GameMode),
+    BuildConstruction(Vec2, ConstructionKind),
+    DemolishConstruction(Vec2),
+    ConnectConstructions(Vec2, Vec2),
+    CutConnection(Vec2, Vec2),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedGameEvent {
+    /// Tick in which the event was processed, counted from the start of the recording
+    pub tick: u64,
+    pub event: RecordedEvent,
+}
+
+/// A game from the snapshot it started with to the hash of the state it ended in. Replaying the
+/// events on the snapshot has to end in the very same state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRecording {
+    pub snapshot: GameSnapshot,
+    pub events: Vec<RecordedGameEvent>,
+    /// Number of ticks from the start to the end of the recording
+    pub ticks: u64,
+    pub end_state_hash: u64,
+}
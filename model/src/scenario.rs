@@ -0,0 +1,167 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    construction::{ConstructionKind, ConstructionStatus},
+    game_configuration::{self, ConnectionRangeRule, GameConfiguration},
+    map::{Deposit, Obstacle},
+};
+
+/// Starting layout of a game, as written down in a scenario file. Everything but the
+/// constructions can be left out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub constructions: Vec<ScenarioConstruction>,
+    /// Ids of the two constructions each connection is between
+    #[serde(default)]
+    pub connections: Vec<(u32, u32)>,
+    #[serde(default)]
+    pub deposits: Vec<Deposit>,
+    #[serde(default)]
+    pub obstacles: Vec<Obstacle>,
+    /// Material the player starts with, in place of the configured starting material
+    #[serde(default)]
+    pub starting_material: Option<f64>,
+    #[serde(default)]
+    pub configuration: GameConfigurationOverrides,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioConstruction {
+    /// Refers to the construction within the scenario file
+    pub id: u32,
+    pub kind: ConstructionKind,
+    pub location: Vec2,
+    #[serde(default = "operating")]
+    pub status: ConstructionStatus,
+}
+
+fn operating() -> ConstructionStatus {
+    ConstructionStatus::Operating
+}
+
+/// Values of the game configuration a scenario replaces, the others stay as configured
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfigurationOverrides {
+    pub connection_capacity: Option<f64>,
+    pub connection_range_rule: Option<ConnectionRangeRule>,
+    pub connection_material_cost: Option<f64>,
+    pub demolition_refund: Option<f64>,
+    #[serde(deserialize_with = "game_configuration::deserialize_optional_ticks_per_second")]
+    pub ticks_per_second: Option<f64>,
+    pub zoom_scale_min: Option<f32>,
+    pub zoom_scale_max: Option<f32>,
+}
+
+impl GameConfigurationOverrides {
+    pub fn apply_to(&self, game_configuration: &mut GameConfiguration) {
+        fn apply<T: Clone>(value: &mut T, override_value: &Option<T>) {
+            if let Some(override_value) = override_value {
+                *value = override_value.clone();
+            }
+        }
+
+        apply(
+            &mut game_configuration.connection_capacity,
+            &self.connection_capacity,
+        );
+        apply(
+            &mut game_configuration.connection_range_rule,
+            &self.connection_range_rule,
+        );
+        apply(
+            &mut game_configuration.connection_material_cost,
+            &self.connection_material_cost,
+        );
+        apply(
+            &mut game_configuration.demolition_refund,
+            &self.demolition_refund,
+        );
+        apply(
+            &mut game_configuration.ticks_per_second,
+            &self.ticks_per_second,
+        );
+        apply(&mut game_configuration.zoom_scale_min, &self.zoom_scale_min);
+        apply(&mut game_configuration.zoom_scale_max, &self.zoom_scale_max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration_file::load_ron_file;
+    use std::path::Path;
+
+    #[test]
+    fn test_parse_scenario() {
+        let scenario: Scenario = ron::from_str(
+            r#"(
+                constructions: [
+                    (id: 1, kind: "Base", location: (0., 0.)),
+                    (id: 2, kind: "Collector", location: (0., 100.), status: Building(progress: 0.5)),
+                ],
+                connections: [(1, 2)],
+                starting_material: Some(250.),
+                configuration: (connection_capacity: Some(20.)),
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ConstructionStatus::Operating,
+            scenario.constructions[0].status
+        );
+        assert_eq!(
+            ConstructionStatus::Building { progress: 0.5 },
+            scenario.constructions[1].status
+        );
+        assert!(scenario.deposits.is_empty());
+
+        let mut game_configuration = GameConfiguration::default();
+        scenario.configuration.apply_to(&mut game_configuration);
+        assert_eq!(
+            GameConfiguration {
+                connection_capacity: 20.,
+                ..GameConfiguration::default()
+            },
+            game_configuration
+        );
+    }
+
+    #[test]
+    fn test_parse_scenario_invalid_ticks_per_second() {
+        for ticks_per_second in ["0.", "-30."] {
+            let scenario = ron::from_str::<Scenario>(&format!(
+                "(constructions: [], configuration: (ticks_per_second: Some({})))",
+                ticks_per_second
+            ));
+            assert!(scenario.is_err(), "{}", ticks_per_second);
+        }
+
+        let scenario: Scenario =
+            ron::from_str("(constructions: [], configuration: (ticks_per_second: Some(60.)))")
+                .unwrap();
+        assert_eq!(Some(60.), scenario.configuration.ticks_per_second);
+    }
+
+    /// Connections of the scenarios that come with the game refer to existing constructions
+    #[test]
+    fn test_load_scenario_files() {
+        for file_name in ["milestone-01.ron", "milestone-02.ron", "milestone-04.ron"] {
+            let path = Path::new("../assets/scenarios").join(file_name);
+            let scenario: Scenario = load_ron_file(&path).unwrap();
+
+            let has_construction = |id: &u32| {
+                scenario
+                    .constructions
+                    .iter()
+                    .any(|construction| construction.id == *id)
+            };
+            assert!(scenario
+                .connections
+                .iter()
+                .all(|(id1, id2)| has_construction(id1) && has_construction(id2)));
+        }
+    }
+}
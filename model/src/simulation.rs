@@ -0,0 +1,213 @@
+use bevy::ecs::event::Events;
+use bevy::ecs::schedule::{ParallelSystemDescriptorCoercion, ShouldRun, SystemLabelId};
+use bevy::ecs::system::{AsSystemLabel, Resource};
+use bevy::prelude::*;
+
+/// Upper bound of ticks per frame, so a long frame does not make the simulation fall further and
+/// further behind
+const MAX_TICKS_PER_FRAME: u32 = 10;
+
+/// Stage after `CoreStage::Update` in which all simulation systems run, once per tick
+#[derive(Debug, Clone, PartialEq, Eq, Hash, StageLabel)]
+pub struct SimulationStage;
+
+/// How the simulation clock decides the number of ticks of a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockMode {
+    /// As many ticks as fit into the real time that passed
+    RealTime,
+    /// Exactly one tick per frame, so that tests and tools can step through the simulation
+    Stepped,
+}
+
+/// Drives the fixed timestep of the simulation. Every tick advances the game by the same
+/// `tick_duration`, independent of the frame rate, so that the same game events always lead to
+/// the same outcome.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulationClock {
+    pub mode: ClockMode,
+    /// Number of ticks run so far. While a tick runs, this is the number of that tick.
+    pub tick: u64,
+    /// Seconds of game time that pass with each tick
+    pub tick_duration: f64,
+    /// Real time that passed since the last tick
+    accumulator: f64,
+    /// Ticks run during the current frame
+    frame_ticks: u32,
+}
+
+impl SimulationClock {
+    pub fn new(ticks_per_second: f64) -> Self {
+        Self {
+            mode: ClockMode::RealTime,
+            tick: 0,
+            tick_duration: 1. / ticks_per_second,
+            accumulator: 0.,
+            frame_ticks: 0,
+        }
+    }
+
+    pub fn stepped(ticks_per_second: f64) -> Self {
+        Self {
+            mode: ClockMode::Stepped,
+            ..Self::new(ticks_per_second)
+        }
+    }
+
+    /// How far the real time has moved on from the last tick towards the next one, from 0 to 1.
+    /// Rendering uses it to interpolate between the states of the last two ticks.
+    pub fn overstep_fraction(&self) -> f32 {
+        (self.accumulator / self.tick_duration) as f32
+    }
+}
+
+/// Lets the simulation stage run once per tick that is due in the current frame
+pub(crate) fn simulation_tick_run_criteria(
+    time: Res<Time>,
+    mut clock: ResMut<SimulationClock>,
+    mut looping: Local<bool>,
+) -> ShouldRun {
+    if !*looping {
+        let frame_time = match clock.mode {
+            ClockMode::RealTime => time.delta_seconds_f64(),
+            ClockMode::Stepped => clock.tick_duration,
+        };
+        let max_accumulator = clock.tick_duration * MAX_TICKS_PER_FRAME as f64;
+        clock.accumulator = (clock.accumulator + frame_time).min(max_accumulator);
+        clock.frame_ticks = 0;
+    }
+
+    if clock.accumulator >= clock.tick_duration {
+        clock.accumulator -= clock.tick_duration;
+        clock.tick += 1;
+        clock.frame_ticks += 1;
+        *looping = true;
+        ShouldRun::YesAndCheckAgain
+    } else {
+        *looping = false;
+        ShouldRun::No
+    }
+}
+
+/// Swaps the event buffers only after frames in which the simulation ticked. Events sent between
+/// two ticks would otherwise be dropped before any simulation system could read them.
+pub(crate) fn update_simulation_events_system<T: Resource>(
+    clock: Res<SimulationClock>,
+    mut events: ResMut<Events<T>>,
+) {
+    if clock.frame_ticks > 0 {
+        events.update();
+    }
+}
+
+/// Label of the system that was added to the simulation stage last
+struct LastSimulationSystem(SystemLabelId);
+
+pub trait SimulationApp {
+    /// Registers events that are read by the simulation, in place of `App::add_event`
+    fn add_simulation_event<T: Resource>(&mut self) -> &mut Self;
+
+    /// Adds a system to the `SimulationStage` that runs after all systems added before. A stage
+    /// orders systems without explicit dependencies differently in every app, which would let
+    /// the same game events lead to different outcomes.
+    fn add_simulation_system<Params, Marker>(
+        &mut self,
+        system: impl ParallelSystemDescriptorCoercion<Params> + AsSystemLabel<Marker>,
+    ) -> &mut Self;
+}
+
+impl SimulationApp for App {
+    fn add_simulation_event<T: Resource>(&mut self) -> &mut Self {
+        if !self.world.contains_resource::<Events<T>>() {
+            self.init_resource::<Events<T>>()
+                .add_system_to_stage(CoreStage::First, update_simulation_events_system::<T>);
+        }
+        self
+    }
+
+    fn add_simulation_system<Params, Marker>(
+        &mut self,
+        system: impl ParallelSystemDescriptorCoercion<Params> + AsSystemLabel<Marker>,
+    ) -> &mut Self {
+        let label = system.as_system_label();
+        let previous_label = self
+            .world
+            .get_resource::<LastSimulationSystem>()
+            .map(|last_system| last_system.0);
+        self.insert_resource(LastSimulationSystem(label));
+
+        match previous_label {
+            Some(previous_label) => {
+                self.add_system_to_stage(SimulationStage, system.after(previous_label))
+            }
+            None => self.add_system_to_stage(SimulationStage, system.label(label)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stepped_clock_ticks_once_per_frame() {
+        let mut app = App::new();
+        app.insert_resource(Time::default())
+            .insert_resource(SimulationClock::stepped(30.))
+            .init_resource::<TickCount>()
+            .add_stage_after(
+                CoreStage::Update,
+                SimulationStage,
+                SystemStage::parallel().with_run_criteria(simulation_tick_run_criteria),
+            )
+            .add_system_to_stage(SimulationStage, count_ticks_system);
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(3, app.world.resource::<TickCount>().0);
+        assert_eq!(3, app.world.resource::<SimulationClock>().tick);
+        assert_eq!(
+            0.,
+            app.world.resource::<SimulationClock>().overstep_fraction()
+        );
+    }
+
+    #[test]
+    fn test_simulation_systems_run_in_the_order_they_were_added() {
+        for _ in 0..5 {
+            let mut app = App::new();
+            app.insert_resource(Time::default())
+                .insert_resource(SimulationClock::stepped(30.))
+                .init_resource::<SystemRuns>()
+                .add_stage_after(
+                    CoreStage::Update,
+                    SimulationStage,
+                    SystemStage::single_threaded().with_run_criteria(simulation_tick_run_criteria),
+                )
+                .add_simulation_system(run_system::<0>)
+                .add_simulation_system(run_system::<1>)
+                .add_simulation_system(run_system::<2>)
+                .add_simulation_system(run_system::<3>)
+                .add_simulation_system(run_system::<4>)
+                .add_simulation_system(run_system::<5>);
+
+            app.update();
+            assert_eq!(vec![0, 1, 2, 3, 4, 5], app.world.resource::<SystemRuns>().0);
+        }
+    }
+
+    #[derive(Default)]
+    struct TickCount(u32);
+
+    fn count_ticks_system(mut tick_count: ResMut<TickCount>) {
+        tick_count.0 += 1;
+    }
+
+    #[derive(Default)]
+    struct SystemRuns(Vec<usize>);
+
+    fn run_system<const INDEX: usize>(mut system_runs: ResMut<SystemRuns>) {
+        system_runs.0.push(INDEX);
+    }
+}
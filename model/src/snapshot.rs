@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    construction::Construction,
+    game::GameMode,
+    game_configuration::GameConfiguration,
+    map::{Deposit, Obstacle},
+    resources::PlayerResources,
+    terrain::Terrain,
+};
+
+/// Everything that is needed to continue a game later on. Entities are replaced by ids that are
+/// only meaningful within the snapshot, as loading spawns new entities. The construction
+/// definitions are not part of it, they come from their file as usual.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub game_configuration: GameConfiguration,
+    pub game_mode: GameMode,
+    pub player_resources: PlayerResources,
+    pub terrain: Terrain,
+    /// Placed constructions, without the one hovering in build mode
+    pub constructions: Vec<ConstructionSnapshot>,
+    /// Ids of the two constructions each connection is between
+    pub connections: Vec<(u64, u64)>,
+    pub deposits: Vec<Deposit>,
+    pub obstacles: Vec<Obstacle>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstructionSnapshot {
+    pub id: u64,
+    pub construction: Construction,
+    /// Progress of the `ProductionCycle`, for constructions that produce material
+    #[serde(default)]
+    pub production_progress: Option<f32>,
+    /// Index into the `deposits` of the snapshot of the deposit the construction mines
+    #[serde(default)]
+    pub mined_deposit: Option<usize>,
+    /// Whether the construction is `Idle`
+    #[serde(default)]
+    pub idle: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::construction::ConstructionStatus;
+    use bevy::prelude::Vec2;
+
+    #[test]
+    fn test_ron_round_trip() {
+        let snapshot = GameSnapshot {
+            game_configuration: GameConfiguration::default(),
+            game_mode: GameMode::Building("Collector".into()),
+            player_resources: PlayerResources {
+                material_available: 12.5,
+                material_rate_per_second: 0.2,
+                energy_need: 4.,
+                energy_available: 7.,
+            },
+            terrain: Terrain::default(),
+            constructions: vec![
+                ConstructionSnapshot {
+                    id: 1,
+                    construction: Construction::default(),
+                    production_progress: None,
+                    mined_deposit: None,
+                    idle: false,
+                },
+                ConstructionSnapshot {
+                    id: 2,
+                    construction: Construction {
+                        location: Vec2::new(100., -20.),
+                        kind: "Extractor".into(),
+                        status: ConstructionStatus::Building { progress: 0.25 },
+                    },
+                    production_progress: Some(0.5),
+                    mined_deposit: Some(0),
+                    idle: true,
+                },
+            ],
+            connections: vec![(1, 2)],
+            deposits: vec![Deposit {
+                location: Vec2::new(100., -20.),
+                remaining: 150.,
+            }],
+            obstacles: vec![Obstacle {
+                location: Vec2::new(-50., 0.),
+                radius: 20.,
+            }],
+        };
+
+        let content = ron::to_string(&snapshot).unwrap();
+        assert_eq!(snapshot, ron::from_str(&content).unwrap());
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Kind of area on the map, which makes constructions built on it more or less productive
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TerrainRegion {
+    pub name: String,
+    pub material_output_factor: f64,
+    pub energy_output_factor: f64,
+    /// Red, green, blue and alpha, each between 0 and 1
+    pub color: [f32; 4],
+    #[serde(default)]
+    pub decoration: Option<TileDecoration>,
+}
+
+/// Small shapes scattered over each tile of a region, they do not affect the game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TileDecoration {
+    /// Number of shapes per tile
+    pub count: usize,
+    pub radius: f32,
+    /// Red, green, blue and alpha, each between 0 and 1
+    pub color: [f32; 4],
+}
+
+/// The map, split into square tiles that each belong to one region. Locations outside of the
+/// tiles are neutral and do not modify any output.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "TerrainDescription", into = "TerrainDescription")]
+pub struct Terrain {
+    pub regions: Vec<TerrainRegion>,
+    /// World location of the lower left corner of the first tile
+    pub origin: Vec2,
+    pub tile_size: f32,
+    pub columns: usize,
+    pub rows: usize,
+    /// Index into `regions` for each tile, row by row starting at the bottom
+    pub tiles: Vec<usize>,
+}
+
+impl Terrain {
+    pub fn region_at(&self, location: Vec2) -> Option<&TerrainRegion> {
+        if self.tile_size <= 0. {
+            return None;
+        }
+
+        let tile = ((location - self.origin) / self.tile_size).floor();
+        if tile.x < 0. || tile.y < 0. {
+            return None;
+        }
+
+        let (column, row) = (tile.x as usize, tile.y as usize);
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+
+        self.tiles
+            .get(row * self.columns + column)
+            .and_then(|region| self.regions.get(*region))
+    }
+
+    pub fn material_output_factor(&self, location: Vec2) -> f64 {
+        self.region_at(location)
+            .map_or(1., |region| region.material_output_factor)
+    }
+
+    pub fn energy_output_factor(&self, location: Vec2) -> f64 {
+        self.region_at(location)
+            .map_or(1., |region| region.energy_output_factor)
+    }
+
+    /// Size of the whole map in world units
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.columns as f32, self.rows as f32) * self.tile_size
+    }
+}
+
+/// Terrain as it is written down in a file: Each region is drawn as a symbol in a grid of rows.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerrainDescription {
+    pub tile_size: f32,
+    /// World location of the lower left corner of the map
+    pub origin: (f32, f32),
+    pub regions: Vec<(char, TerrainRegion)>,
+    /// One symbol per tile, top row first
+    pub rows: Vec<String>,
+}
+
+impl TryFrom<TerrainDescription> for Terrain {
+    type Error = String;
+
+    fn try_from(description: TerrainDescription) -> Result<Self, Self::Error> {
+        let symbol_to_region_map: HashMap<char, usize> = description
+            .regions
+            .iter()
+            .enumerate()
+            .map(|(region, (symbol, _))| (*symbol, region))
+            .collect();
+
+        let rows = description.rows.len();
+        let columns = description
+            .rows
+            .first()
+            .map_or(0, |row| row.chars().count());
+
+        let mut tiles = Vec::with_capacity(rows * columns);
+        for (row_number, row) in description.rows.iter().rev().enumerate() {
+            if row.chars().count() != columns {
+                return Err(format!(
+                    "Terrain row {} has {} tiles instead of {}",
+                    rows - row_number,
+                    row.chars().count(),
+                    columns
+                ));
+            }
+
+            for symbol in row.chars() {
+                match symbol_to_region_map.get(&symbol) {
+                    Some(region) => tiles.push(*region),
+                    None => return Err(format!("Unknown terrain region '{}'", symbol)),
+                }
+            }
+        }
+
+        Ok(Terrain {
+            regions: description
+                .regions
+                .into_iter()
+                .map(|(_, region)| region)
+                .collect(),
+            origin: Vec2::new(description.origin.0, description.origin.1),
+            tile_size: description.tile_size,
+            columns,
+            rows,
+            tiles,
+        })
+    }
+}
+
+/// Writes the terrain down with a letter per region, e.g. to store it in a snapshot of a game
+impl From<Terrain> for TerrainDescription {
+    fn from(terrain: Terrain) -> Self {
+        let symbol = |region: usize| char::from_u32('A' as u32 + region as u32).unwrap_or('?');
+        let rows = terrain
+            .tiles
+            .chunks(terrain.columns.max(1))
+            .rev()
+            .map(|row| row.iter().map(|region| symbol(*region)).collect())
+            .collect();
+
+        TerrainDescription {
+            tile_size: terrain.tile_size,
+            origin: (terrain.origin.x, terrain.origin.y),
+            regions: terrain
+                .regions
+                .into_iter()
+                .enumerate()
+                .map(|(region, terrain_region)| (symbol(region), terrain_region))
+                .collect(),
+            rows,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Map: B R
+    #[test]
+    fn test_region_at() {
+        let terrain = Terrain {
+            regions: vec![region("Barren", 0.5), region("Rich ore", 2.)],
+            origin: Vec2::new(-10., -5.),
+            tile_size: 10.,
+            columns: 2,
+            rows: 1,
+            tiles: vec![0, 1],
+        };
+
+        assert_eq!(0.5, terrain.material_output_factor(Vec2::new(-5., 0.)));
+        assert_eq!(2., terrain.material_output_factor(Vec2::new(5., 0.)));
+        assert_eq!(1., terrain.material_output_factor(Vec2::new(15., 0.)));
+        assert_eq!(1., terrain.material_output_factor(Vec2::new(0., -10.)));
+    }
+
+    #[test]
+    fn test_terrain_from_description() {
+        let description = TerrainDescription {
+            tile_size: 10.,
+            origin: (0., 0.),
+            regions: vec![('B', region("Barren", 0.5)), ('R', region("Rich ore", 2.))],
+            rows: vec!["RB".to_string(), "BB".to_string()],
+        };
+
+        let terrain = Terrain::try_from(description.clone()).unwrap();
+        assert_eq!(vec![0, 0, 1, 0], terrain.tiles);
+        assert_eq!(2., terrain.material_output_factor(Vec2::new(5., 15.)));
+
+        let invalid_description = TerrainDescription {
+            rows: vec!["RB".to_string(), "BX".to_string()],
+            ..description
+        };
+        assert_eq!(
+            Err("Unknown terrain region 'X'".to_string()),
+            Terrain::try_from(invalid_description)
+        );
+    }
+
+    #[test]
+    fn test_terrain_to_description() {
+        let terrain = Terrain {
+            regions: vec![region("Barren", 0.5), region("Rich ore", 2.)],
+            origin: Vec2::new(-10., -5.),
+            tile_size: 10.,
+            columns: 2,
+            rows: 2,
+            tiles: vec![0, 0, 1, 0],
+        };
+
+        let description = TerrainDescription::from(terrain.clone());
+        assert_eq!(vec!["BA".to_string(), "AA".to_string()], description.rows);
+        assert_eq!(Ok(terrain), Terrain::try_from(description));
+    }
+
+    fn region(name: &str, material_output_factor: f64) -> TerrainRegion {
+        TerrainRegion {
+            name: name.to_string(),
+            material_output_factor,
+            energy_output_factor: 1.,
+            color: [1., 1., 1., 1.],
+            decoration: None,
+        }
+    }
+}
@@ -1,9 +1,16 @@
 use bevy::prelude::*;
 use model::{
-    construction::{Construction, ConstructionStatus},
-    game::GameEvent,
+    connection::Connection,
+    construction::{
+        Construction, ConstructionStatus, EnergySatisfaction, Idle, MinedDeposit, ProductionCycle,
+    },
+    construction_registry::ConstructionRegistry,
+    energy_network::EnergyNetworks,
     game_configuration::GameConfiguration,
+    map::Deposit,
     resources::PlayerResources,
+    simulation::{SimulationApp, SimulationClock},
+    terrain::Terrain,
 };
 
 pub struct ResourcesPlugin;
@@ -11,15 +18,20 @@ pub struct ResourcesPlugin;
 impl Plugin for ResourcesPlugin {
     fn build(&self, app: &mut App) {
         app.add_startup_system(init_system)
-            .add_system(update_available_resources)
-            .add_system(on_game_event_resource_system);
+            .add_simulation_system(on_construction_enter_system)
+            .add_simulation_system(update_available_resources)
+            .add_simulation_system(material_production_system);
     }
 }
 
-fn init_system(mut commands: Commands, init_player_resources: Option<Res<PlayerResources>>) {
+fn init_system(
+    mut commands: Commands,
+    init_player_resources: Option<Res<PlayerResources>>,
+    game_configuration: Res<GameConfiguration>,
+) {
     if let None = init_player_resources {
         commands.insert_resource(PlayerResources {
-            material_available: 100.,
+            material_available: game_configuration.starting_material,
             material_rate_per_second: 0.,
             energy_need: 0.,
             energy_available: 0.,
@@ -28,54 +40,202 @@ fn init_system(mut commands: Commands, init_player_resources: Option<Res<PlayerR
 }
 
 fn update_available_resources(
-    time: Res<Time>,
-    query: Query<&Construction>,
+    query: Query<(
+        Entity,
+        &Construction,
+        Option<&EnergySatisfaction>,
+        Option<&Idle>,
+    )>,
+    connection_query: Query<&Connection>,
     mut player_resources: ResMut<PlayerResources>,
-    game_configuration: Res<GameConfiguration>,
+    mut energy_networks: ResMut<EnergyNetworks>,
+    construction_registry: Res<ConstructionRegistry>,
+    terrain: Res<Terrain>,
 ) {
-    let time_delta = time.delta().as_secs_f64();
-
-    // material_rate_per_second & material_available
-    let material_rate_per_second = query
+    // Summed up in the order of the locations, so that the result does not depend on entity ids
+    let mut operating_constructions: Vec<_> = query
         .iter()
-        .filter(|construction| construction.status == ConstructionStatus::Operating)
-        .fold(0., |acc, construction| {
-            acc + game_configuration.material_output(&construction.kind)
-        });
+        .filter(|(_, construction, _, idle)| {
+            construction.status == ConstructionStatus::Operating && idle.is_none()
+        })
+        .collect();
+    operating_constructions.sort_by(|(_, construction1, _, _), (_, construction2, _, _)| {
+        logic::construction::compare_locations(&construction1.location, &construction2.location)
+    });
+
+    // material_rate_per_second
+    let material_rate_per_second = operating_constructions.iter().fold(
+        0.,
+        |acc, (_, construction, energy_satisfaction, _)| {
+            acc + logic::terrain::effective_material_output(
+                &construction_registry,
+                &terrain,
+                construction,
+            ) * production_factor(&construction_registry, construction, *energy_satisfaction)
+        },
+    );
     player_resources.as_mut().material_rate_per_second = material_rate_per_second;
-    player_resources.as_mut().material_available += time_delta * material_rate_per_second;
+
+    // Energy is balanced per network, as it cannot flow between unconnected constructions. Next to
+    // operating constructions, construction sites draw energy as well, idle constructions do not
+    let mut constructions: Vec<_> = query
+        .iter()
+        .filter(|(_, construction, _, idle)| {
+            construction.status != ConstructionStatus::Hovering && idle.is_none()
+        })
+        .map(|(entity, construction, _, _)| (entity, construction))
+        .collect();
+    logic::construction::sort_by_location(&mut constructions);
+    let connections = connection_query.iter().collect();
+    energy_networks.as_mut().0 = logic::energy_network::find_energy_networks(
+        &construction_registry,
+        &terrain,
+        &constructions,
+        &connections,
+    );
 
     // energy_need
-    let energy_need = query
+    let energy_need = energy_networks
+        .0
         .iter()
-        .filter(|construction| construction.status == ConstructionStatus::Operating)
-        .fold(0., |acc, construction| {
-            acc + game_configuration.energy_input(&construction.kind)
-        });
+        .fold(0., |acc, network| acc + network.energy_demand);
     player_resources.as_mut().energy_need = energy_need;
 
     // energy_available
-    let energy_available = query
+    let energy_available = energy_networks
+        .0
         .iter()
-        .filter(|construction| construction.status == ConstructionStatus::Operating)
-        .fold(0., |acc, construction| {
-            acc + game_configuration.energy_output(&construction.kind)
-        });
+        .fold(0., |acc, network| acc + network.energy_supply);
     player_resources.as_mut().energy_available = energy_available;
 }
 
-fn on_game_event_resource_system(
-    mut game_events: EventReader<GameEvent>,
+/// Constructions whose production cycles advance, along with what they need for it
+type ProducingConstruction<'a> = (
+    Entity,
+    &'a Construction,
+    Option<&'a EnergySatisfaction>,
+    Option<&'a MinedDeposit>,
+    &'a mut ProductionCycle,
+);
+
+/// Advances the production cycles of all operating constructions. Material is only delivered in
+/// whole units, once a cycle completes. Constructions that mine a deposit drain it and go idle
+/// once less than a whole unit remains.
+fn material_production_system(
+    mut commands: Commands,
+    clock: Res<SimulationClock>,
+    mut query: Query<ProducingConstruction, Without<Idle>>,
+    mut deposit_query: Query<&mut Deposit>,
     mut player_resources: ResMut<PlayerResources>,
-    game_configuration: Res<GameConfiguration>,
+    construction_registry: Res<ConstructionRegistry>,
+    terrain: Res<Terrain>,
 ) {
-    for event in game_events.iter() {
-        match event {
-            &GameEvent::BuildConstruction(_, ref kind) => {
-                player_resources.as_mut().material_available -=
-                    game_configuration.material_build_demand(kind);
+    let time_delta = clock.tick_duration;
+    let mut produced_material_units = 0.;
+
+    for (entity, construction, energy_satisfaction, mined_deposit, mut production_cycle) in
+        query.iter_mut()
+    {
+        if construction.status != ConstructionStatus::Operating {
+            continue;
+        }
+        if construction_registry.requires_deposit(&construction.kind) && mined_deposit.is_none() {
+            continue;
+        }
+
+        let material_rate =
+            logic::terrain::effective_material_output(
+                &construction_registry,
+                &terrain,
+                construction,
+            ) * production_factor(&construction_registry, construction, energy_satisfaction);
+        if material_rate <= 0. {
+            continue;
+        }
+
+        let progress = production_cycle.progress as f64 + time_delta * material_rate;
+        let mut material_units = progress.floor();
+        production_cycle.as_mut().progress = (progress - material_units) as f32;
+
+        if let Some(mined_deposit) = mined_deposit.filter(|_| material_units > 0.) {
+            match deposit_query.get_mut(mined_deposit.0) {
+                Ok(mut deposit) => {
+                    material_units = material_units.min(deposit.remaining.floor());
+                    deposit.as_mut().remaining -= material_units;
+                    if deposit.remaining < 1. {
+                        debug!("Deposit {:?} ran dry", mined_deposit.0);
+                        commands.entity(mined_deposit.0).despawn();
+                        commands.entity(entity).insert(Idle);
+                    }
+                }
+                Err(_) => {
+                    material_units = 0.;
+                    commands.entity(entity).insert(Idle);
+                }
             }
-            _ => (),
         }
+
+        produced_material_units += material_units;
+    }
+
+    // Whole units add up exactly in any order, unlike adding them to the fractional material one
+    // by one
+    player_resources.as_mut().material_available += produced_material_units;
+}
+
+/// New constructions, along with the production state a loaded game may have restored
+type EnteringConstruction<'a> = (
+    Entity,
+    &'a Construction,
+    Option<&'a ProductionCycle>,
+    Option<&'a MinedDeposit>,
+    Option<&'a Idle>,
+);
+
+/// Constructions that produce material get a production cycle, unless a loaded game restored it
+/// already. Those that require a deposit mine the closest one in reach, or stay idle if there is
+/// none, again unless a loaded game restored either.
+fn on_construction_enter_system(
+    mut commands: Commands,
+    query: Query<EnteringConstruction, Added<Construction>>,
+    deposit_query: Query<(Entity, &Deposit)>,
+    construction_registry: Res<ConstructionRegistry>,
+) {
+    for (entity, construction, production_cycle, mined_deposit, idle) in query.iter() {
+        if construction_registry.material_output(&construction.kind) > 0.
+            && production_cycle.is_none()
+        {
+            commands.entity(entity).insert(ProductionCycle::default());
+        }
+
+        if construction_registry.requires_deposit(&construction.kind)
+            && construction.status != ConstructionStatus::Hovering
+            && mined_deposit.is_none()
+            && idle.is_none()
+        {
+            let deposit = logic::construction::deposit_at(
+                deposit_query.iter(),
+                construction.location,
+                construction_registry.influence_radius(&construction.kind),
+            );
+            match deposit {
+                Some(deposit) => commands.entity(entity).insert(MinedDeposit(deposit)),
+                None => commands.entity(entity).insert(Idle),
+            };
+        }
+    }
+}
+
+/// Constructions that depend on energy only produce in proportion to the energy they receive.
+/// A consumer without an `EnergySatisfaction` yet did not get any energy so far.
+fn production_factor(
+    construction_registry: &ConstructionRegistry,
+    construction: &Construction,
+    energy_satisfaction: Option<&EnergySatisfaction>,
+) -> f64 {
+    if construction_registry.energy_input(&construction.kind) > 0. {
+        energy_satisfaction.map_or(0., |energy_satisfaction| energy_satisfaction.0)
+    } else {
+        1.
     }
 }